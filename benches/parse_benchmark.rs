@@ -6,6 +6,10 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("parse", |b| {
         b.iter(|| parse("0 0 * * Wed-Fri", &Utc::now()));
     });
+
+    c.bench_function("parse sparse", |b| {
+        b.iter(|| parse("59 23 31 12 *", &Utc::now()));
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
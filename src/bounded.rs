@@ -0,0 +1,260 @@
+use crate::{
+    Occurrences, ParseError, Recurrence,
+    combine::{Bounds, bounds},
+};
+use chrono::{DateTime, TimeZone, Utc};
+use core::ops::{Bound, RangeBounds};
+
+/// Dates of a recurrence within a period and up to a number of runs, created
+/// with [`Recurrence::not_before`](trait.Recurrence.html#method.not_before),
+/// [`Recurrence::not_after`](trait.Recurrence.html#method.not_after) or
+/// [`Recurrence::max_runs`](trait.Recurrence.html#method.max_runs)
+///
+/// Searching past the bounds fails with
+/// [`ParseError::Exhausted`](enum.ParseError.html) instead of returning a date
+/// outside of them, and iterating stops at the last date.
+///
+/// Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron_parser::{ParseError, Recurrence, Schedule};
+///
+/// let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap();
+/// let schedule: Schedule = "0 9 * * Mon".parse().unwrap();
+/// let campaign = schedule.not_after(&end).max_runs(&start, 3);
+///
+/// let runs: Vec<_> = campaign.occurrences_between(start..).unwrap().collect();
+/// assert_eq!(runs.len(), 3);
+/// let last = runs.last().unwrap();
+/// assert_eq!(*last, Utc.with_ymd_and_hms(2024, 3, 18, 9, 0, 0).unwrap());
+/// assert!(matches!(campaign.next_after(last), Err(ParseError::Exhausted)));
+/// assert!(matches!(campaign.prev_before(&start), Err(ParseError::Exhausted)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bounded<R> {
+    recurrence: R,
+    not_before: Option<DateTime<Utc>>,
+    not_after: Option<DateTime<Utc>>,
+    max_runs: Option<u32>,
+}
+
+impl<R> Bounded<R> {
+    pub(crate) const fn new(recurrence: R) -> Self {
+        Self {
+            recurrence,
+            not_before: None,
+            not_after: None,
+            max_runs: None,
+        }
+    }
+
+    /// Ignore the dates before `dt`
+    #[must_use]
+    pub fn not_before<TZ: TimeZone>(mut self, dt: &DateTime<TZ>) -> Self {
+        self.not_before = Some(dt.with_timezone(&Utc));
+        self
+    }
+
+    /// Ignore the dates after `dt`
+    #[must_use]
+    pub fn not_after<TZ: TimeZone>(mut self, dt: &DateTime<TZ>) -> Self {
+        self.not_after = Some(dt.with_timezone(&Utc));
+        self
+    }
+
+    /// Keep the first `runs` dates from `dt`, which replaces
+    /// [`not_before`](#method.not_before)
+    ///
+    /// The runs are counted from `dt` in the timezone of each search.
+    #[must_use]
+    pub fn max_runs<TZ: TimeZone>(self, dt: &DateTime<TZ>, runs: u32) -> Self {
+        let mut bounded = self.not_before(dt);
+        bounded.max_runs = Some(runs);
+        bounded
+    }
+}
+
+impl<R: Recurrence> Bounded<R> {
+    // the first date allowed, in `tz`
+    fn start<TZ: TimeZone>(&self, tz: &TZ) -> Bound<DateTime<TZ>> {
+        self.not_before
+            .map_or(Bound::Unbounded, |dt| Bound::Included(dt.with_timezone(tz)))
+    }
+
+    // the last date allowed, in `tz`, the last run when they are limited
+    fn end<TZ: TimeZone>(&self, tz: &TZ) -> Result<Bound<DateTime<TZ>>, ParseError> {
+        let not_after = self
+            .not_after
+            .map_or(Bound::Unbounded, |dt| Bound::Included(dt.with_timezone(tz)));
+        let (Some(runs), Some(not_before)) = (self.max_runs, self.not_before) else {
+            return Ok(not_after);
+        };
+
+        let mut from = Bound::Included(not_before.with_timezone(tz));
+        for _ in 0..runs {
+            match self
+                .recurrence
+                .first_in((from.clone(), not_after.clone()))?
+            {
+                Some(run) => from = Bound::Excluded(run),
+                // fewer dates than runs
+                None => return Ok(not_after),
+            }
+        }
+        Ok(match from {
+            Bound::Excluded(last) => Bound::Included(last),
+            // no runs
+            start => exclude(start),
+        })
+    }
+
+    // the part of `range` within the bounds, in the timezone of `tz`
+    fn clamp<TZ: TimeZone>(&self, tz: &TZ, range: Bounds<TZ>) -> Result<Bounds<TZ>, ParseError> {
+        let (start, end) = range;
+        let start = later_start(start, self.start(tz));
+        let end = earlier_end(end, self.end(tz)?);
+        Ok((start, end))
+    }
+
+    // error of a forward search without a date, the search stops at the end
+    // of the bounds unless there is none
+    fn after_end<TZ: TimeZone>(&self, tz: &TZ) -> ParseError {
+        match self.end(tz) {
+            Ok(Bound::Unbounded) => ParseError::HorizonExceeded,
+            Ok(_) => ParseError::Exhausted,
+            Err(err) => err,
+        }
+    }
+
+    // error of a backward search without a date
+    const fn before_start(&self) -> ParseError {
+        if self.not_before.is_some() {
+            ParseError::Exhausted
+        } else {
+            ParseError::HorizonExceeded
+        }
+    }
+}
+
+impl<R: Recurrence> Recurrence for Bounded<R> {
+    fn first_in<TZ: TimeZone, B: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: B,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let range = bounds(&range);
+        let (Bound::Included(from) | Bound::Excluded(from)) = &range.0 else {
+            return Err(ParseError::InvalidRange);
+        };
+        let range = self.clamp(&from.timezone(), range)?;
+        self.recurrence.first_in(range)
+    }
+
+    fn last_in<TZ: TimeZone, B: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: B,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let range = bounds(&range);
+        let (Bound::Included(to) | Bound::Excluded(to)) = &range.1 else {
+            return Err(ParseError::InvalidRange);
+        };
+        let range = self.clamp(&to.timezone(), range)?;
+        self.recurrence.last_in(range)
+    }
+
+    // the bounds are found once, not for every date
+    fn occurrences_between<TZ: TimeZone, B: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: B,
+    ) -> Result<Occurrences<'_, TZ, Self>, ParseError> {
+        let range = bounds(&range);
+        let (Bound::Included(from) | Bound::Excluded(from)) = &range.0 else {
+            return Err(ParseError::InvalidRange);
+        };
+        let range = self.clamp(&from.timezone(), range)?;
+        let occurrences = Occurrences::new(self, range)?;
+        Ok(occurrences.with_search(|bounded, range| bounded.recurrence.first_in(range)))
+    }
+
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        let range = (Bound::Included(dt.clone()), Bound::Included(dt.clone()));
+        self.recurrence.matches(dt)
+            && self
+                .clamp(&dt.timezone(), range)
+                .is_ok_and(|range| range.contains(dt))
+    }
+
+    fn horizon(&self) -> Option<u32> {
+        self.recurrence.horizon()
+    }
+
+    fn next_after<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> Result<DateTime<TZ>, ParseError> {
+        let next = self.first_in((Bound::Excluded(dt.clone()), Bound::Unbounded))?;
+        next.ok_or_else(|| self.after_end(&dt.timezone()))
+    }
+
+    fn next_at_or_after<TZ: TimeZone>(
+        &self,
+        dt: &DateTime<TZ>,
+    ) -> Result<DateTime<TZ>, ParseError> {
+        let next = self.first_in(dt.clone()..)?;
+        next.ok_or_else(|| self.after_end(&dt.timezone()))
+    }
+
+    fn prev_before<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> Result<DateTime<TZ>, ParseError> {
+        self.last_in(..dt.clone())?
+            .ok_or_else(|| self.before_start())
+    }
+
+    fn prev_at_or_before<TZ: TimeZone>(
+        &self,
+        dt: &DateTime<TZ>,
+    ) -> Result<DateTime<TZ>, ParseError> {
+        self.last_in(..=dt.clone())?
+            .ok_or_else(|| self.before_start())
+    }
+}
+
+// helper function returning the later of two range starts
+fn later_start<TZ: TimeZone>(
+    a: Bound<DateTime<TZ>>,
+    b: Bound<DateTime<TZ>>,
+) -> Bound<DateTime<TZ>> {
+    match (&a, &b) {
+        (_, Bound::Unbounded) => a,
+        (Bound::Unbounded, _) => b,
+        (Bound::Included(x) | Bound::Excluded(x), Bound::Included(y) | Bound::Excluded(y)) => {
+            if x > y || (x == y && matches!(a, Bound::Excluded(_))) {
+                a
+            } else {
+                b
+            }
+        }
+    }
+}
+
+// helper function returning the earlier of two range ends
+fn earlier_end<TZ: TimeZone>(
+    a: Bound<DateTime<TZ>>,
+    b: Bound<DateTime<TZ>>,
+) -> Bound<DateTime<TZ>> {
+    match (&a, &b) {
+        (_, Bound::Unbounded) => a,
+        (Bound::Unbounded, _) => b,
+        (Bound::Included(x) | Bound::Excluded(x), Bound::Included(y) | Bound::Excluded(y)) => {
+            if x < y || (x == y && matches!(a, Bound::Excluded(_))) {
+                a
+            } else {
+                b
+            }
+        }
+    }
+}
+
+// helper function turning a range start into an end excluding every date
+fn exclude<TZ: TimeZone>(start: Bound<DateTime<TZ>>) -> Bound<DateTime<TZ>> {
+    match start {
+        Bound::Included(dt) | Bound::Excluded(dt) => Bound::Excluded(dt),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{Field, FieldError, FieldSet, ParseError, Schedule, schedule::FieldErrors};
+use chrono::Weekday;
+use core::ops::RangeInclusive;
+
+impl Schedule {
+    /// Build a schedule from values instead of an expression, fields that
+    /// aren't set match every value like `*`
+    ///
+    /// Example
+    /// ```
+    /// use chrono::Weekday::{Fri, Mon};
+    /// use cron_parser::Schedule;
+    ///
+    /// let schedule = Schedule::builder()
+    ///     .minutes([0, 30])
+    ///     .hours(9..=17)
+    ///     .weekdays(Mon..=Fri)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(schedule.to_string(), "0,30 9-17 * * 1-5");
+    /// ```
+    #[must_use]
+    pub const fn builder() -> ScheduleBuilder {
+        ScheduleBuilder {
+            minutes: Values::Every,
+            hours: Values::Every,
+            days_of_month: Values::Every,
+            months: Values::Every,
+            days_of_week: Values::Every,
+        }
+    }
+}
+
+/// Builder of a [`Schedule`](struct.Schedule.html), created with
+/// [`Schedule::builder`](struct.Schedule.html#method.builder)
+///
+/// Values are checked by [`build`](#method.build), which returns the same
+/// schedule as parsing the expression writing fields that aren't set as `*`
+/// and the others as lists of values, e.g. `0,30 * * * *` for
+/// `.minutes([0, 30])`. With
+/// [`with_vixie_dst`](struct.Schedule.html#method.with_vixie_dst), it is a
+/// wildcard job when the minutes or the hours aren't set.
+#[derive(Debug, Clone, Copy)]
+pub struct ScheduleBuilder {
+    minutes: Values,
+    hours: Values,
+    days_of_month: Values,
+    months: Values,
+    days_of_week: Values,
+}
+
+impl ScheduleBuilder {
+    /// Minutes, 0 - 59
+    #[must_use]
+    pub fn minutes<I: IntoIterator<Item = u32>>(mut self, minutes: I) -> Self {
+        self.minutes = Values::new(Field::Minute, minutes);
+        self
+    }
+
+    /// Hours, 0 - 23
+    #[must_use]
+    pub fn hours<I: IntoIterator<Item = u32>>(mut self, hours: I) -> Self {
+        self.hours = Values::new(Field::Hour, hours);
+        self
+    }
+
+    /// Days of the month, 1 - 31
+    #[must_use]
+    pub fn days_of_month<I: IntoIterator<Item = u32>>(mut self, days: I) -> Self {
+        self.days_of_month = Values::new(Field::DayOfMonth, days);
+        self
+    }
+
+    /// Months, 1 - 12
+    #[must_use]
+    pub fn months<I: IntoIterator<Item = u32>>(mut self, months: I) -> Self {
+        self.months = Values::new(Field::Month, months);
+        self
+    }
+
+    /// Days of the week, a day, a list or a range such as `Mon..=Fri`
+    #[must_use]
+    pub fn weekdays<W: IntoWeekdays>(mut self, days: W) -> Self {
+        self.days_of_week = Values::Some(days.into_weekdays());
+        self
+    }
+
+    /// Check the values and compile the schedule
+    ///
+    /// # Errors
+    /// [`ParseError::InvalidFields`](enum.ParseError.html), or the error of
+    /// the first field without `alloc`, with `InvalidValue` for a value out of
+    /// the range of its field and `InvalidRange` for a field without values,
+    /// [`ParseError::NeverMatches`](enum.ParseError.html) if the days never
+    /// exist.
+    pub fn build(self) -> Result<Schedule, ParseError> {
+        let mut errors = FieldErrors::default();
+        let mut compile = |field: Field, values: Values| {
+            let error = match values {
+                Values::Every => return (field.min()..=field.max()).collect(),
+                Values::Some(set) if !set.is_empty() => return set,
+                Values::Some(_) => ParseError::InvalidRange,
+                Values::OutOfRange => ParseError::InvalidValue,
+            };
+            errors.push(FieldError { field, error });
+            FieldSet::new()
+        };
+
+        let fields = [
+            compile(Field::Minute, self.minutes),
+            compile(Field::Hour, self.hours),
+            compile(Field::DayOfMonth, self.days_of_month),
+            compile(Field::Month, self.months),
+            compile(Field::DayOfWeek, self.days_of_week),
+        ];
+
+        errors.into_result()?;
+
+        // unset minutes or hours are written `*`
+        let wildcard = matches!(self.minutes, Values::Every) || matches!(self.hours, Values::Every);
+        Schedule::from_fields(fields, wildcard)
+    }
+}
+
+// values of a field, checked against its range when set
+#[derive(Debug, Clone, Copy)]
+enum Values {
+    Every,
+    Some(FieldSet),
+    OutOfRange,
+}
+
+impl Values {
+    fn new<I: IntoIterator<Item = u32>>(field: Field, values: I) -> Self {
+        let mut set = FieldSet::new();
+        for value in values {
+            if value < field.min() || value > field.max() {
+                return Self::OutOfRange;
+            }
+            set.insert(value);
+        }
+        Self::Some(set)
+    }
+}
+
+/// Days of the week accepted by [`ScheduleBuilder::weekdays`](struct.ScheduleBuilder.html#method.weekdays)
+///
+/// Implemented for a single [`Weekday`], arrays and slices of them, and
+/// inclusive ranges, which wrap around the end of the week, e.g. `Fri..=Mon`.
+pub trait IntoWeekdays {
+    /// The days as a set, from 0 for Sunday
+    fn into_weekdays(self) -> FieldSet;
+}
+
+impl IntoWeekdays for Weekday {
+    fn into_weekdays(self) -> FieldSet {
+        [self].into_weekdays()
+    }
+}
+
+impl<const N: usize> IntoWeekdays for [Weekday; N] {
+    fn into_weekdays(self) -> FieldSet {
+        self.as_slice().into_weekdays()
+    }
+}
+
+impl IntoWeekdays for &[Weekday] {
+    fn into_weekdays(self) -> FieldSet {
+        self.iter().map(Weekday::num_days_from_sunday).collect()
+    }
+}
+
+impl IntoWeekdays for RangeInclusive<Weekday> {
+    fn into_weekdays(self) -> FieldSet {
+        let (mut day, last) = self.into_inner();
+        let mut set = FieldSet::new();
+        set.insert(day.num_days_from_sunday());
+        while day != last {
+            day = day.succ();
+            set.insert(day.num_days_from_sunday());
+        }
+        set
+    }
+}
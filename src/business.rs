@@ -0,0 +1,332 @@
+use crate::{
+    FieldSet, IntoWeekdays, MAX_SKIPPED_DATES, ParseError, Recurrence,
+    calendar::start_of_day,
+    combine::{Bounds, bounds},
+};
+use alloc::vec::Vec;
+use chrono::{DateTime, Datelike, Months, NaiveDate, TimeZone};
+use core::{
+    num::NonZeroUsize,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
+
+/// Weekend days and holidays, the other days are business days
+///
+/// The weekend is Saturday and Sunday unless set. Requires the `alloc`
+/// feature.
+///
+/// Example
+/// ```
+/// use chrono::NaiveDate;
+/// use chrono::Weekday::{Fri, Sat};
+/// use cron_parser::BusinessDays;
+///
+/// let day = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+/// let days = BusinessDays::new()
+///     .with_weekend([Fri, Sat])
+///     .with_holiday(day(4, 10))
+///     .with_holidays(day(6, 16)..=day(6, 18));
+/// assert!(days.is_business_day(day(4, 7)));
+/// assert!(!days.is_business_day(day(4, 10)));
+/// assert!(!days.is_business_day(day(4, 12)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BusinessDays {
+    weekend: FieldSet,
+    holidays: Vec<RangeInclusive<NaiveDate>>,
+}
+
+impl Default for BusinessDays {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BusinessDays {
+    /// Monday to Friday, without holidays
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            weekend: [0, 6].into_iter().collect(),
+            holidays: Vec::new(),
+        }
+    }
+
+    /// Replace the weekend days, e.g. `[Fri, Sat]`
+    #[must_use]
+    pub fn with_weekend<W: IntoWeekdays>(mut self, days: W) -> Self {
+        self.weekend = days.into_weekdays();
+        self
+    }
+
+    /// Add a holiday
+    #[must_use]
+    pub fn with_holiday(self, date: NaiveDate) -> Self {
+        self.with_holidays(date..=date)
+    }
+
+    /// Add every day from the start to the end of `dates` as holidays, both
+    /// included
+    #[must_use]
+    pub fn with_holidays(mut self, dates: RangeInclusive<NaiveDate>) -> Self {
+        self.holidays.push(dates);
+        self
+    }
+
+    /// Check if `date` is neither a weekend day nor a holiday
+    #[must_use]
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.weekend.contains(date.weekday().num_days_from_sunday())
+            && !self.holidays.iter().any(|holiday| holiday.contains(&date))
+    }
+
+    // the business day of the month of `date`
+    fn day_in(&self, day: BusinessDay, date: NaiveDate) -> Option<NaiveDate> {
+        let mut days = (1..=31)
+            .filter_map(|day| date.with_day(day))
+            .filter(|date| self.is_business_day(*date));
+        let index = day.n.get().checked_sub(1)?;
+        if day.from_end {
+            days.nth_back(index)
+        } else {
+            days.nth(index)
+        }
+    }
+}
+
+/// Which business day of each month, see
+/// [`Recurrence::on_business_day`](trait.Recurrence.html#method.on_business_day)
+///
+/// Example
+/// ```
+/// use cron_parser::{BusinessDay, ParseError};
+///
+/// assert_eq!(BusinessDay::nth_last(1).unwrap(), BusinessDay::LAST);
+/// assert!(matches!(BusinessDay::nth(0), Err(ParseError::InvalidValue)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BusinessDay {
+    n: NonZeroUsize,
+    from_end: bool,
+}
+
+impl BusinessDay {
+    /// The first business day of the month
+    pub const FIRST: Self = Self {
+        n: NonZeroUsize::MIN,
+        from_end: false,
+    };
+
+    /// The last business day of the month
+    pub const LAST: Self = Self {
+        n: NonZeroUsize::MIN,
+        from_end: true,
+    };
+
+    /// The nth business day of the month, from 1
+    ///
+    /// # Errors
+    /// [`ParseError::InvalidValue`](enum.ParseError.html) if `n` is 0
+    pub const fn nth(n: usize) -> Result<Self, ParseError> {
+        match NonZeroUsize::new(n) {
+            Some(n) => Ok(Self { n, from_end: false }),
+            None => Err(ParseError::InvalidValue),
+        }
+    }
+
+    /// The nth business day from the end of the month, 1 is the last one
+    ///
+    /// # Errors
+    /// [`ParseError::InvalidValue`](enum.ParseError.html) if `n` is 0
+    pub const fn nth_last(n: usize) -> Result<Self, ParseError> {
+        match NonZeroUsize::new(n) {
+            Some(n) => Ok(Self { n, from_end: true }),
+            None => Err(ParseError::InvalidValue),
+        }
+    }
+}
+
+/// Dates of a recurrence on one business day of each month, created with
+/// [`Recurrence::on_business_day`](trait.Recurrence.html#method.on_business_day)
+///
+/// Days are local days in the timezone of the searched dates, and months
+/// without that business day are skipped. A search without an end gives up
+/// after the [`horizon`](trait.Recurrence.html#method.horizon) of the
+/// recurrence, and any search after
+/// [`MAX_SKIPPED_DATES`](constant.MAX_SKIPPED_DATES.html) months, with
+/// [`ParseError::HorizonExceeded`](enum.ParseError.html).
+///
+/// Example
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use cron_parser::{BusinessDay, BusinessDays, Recurrence, Schedule};
+///
+/// let new_year = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+/// let days = BusinessDays::new().with_holiday(new_year);
+/// let schedule: Schedule = "0 9 * * *".parse().unwrap();
+///
+/// // the 3rd business day of each month at 09:00
+/// let third = schedule.clone().on_business_day(BusinessDay::nth(3).unwrap(), days.clone());
+/// let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let next = third.next_after(&now).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 4, 9, 0, 0).unwrap());
+/// let next = third.next_after(&next).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 2, 5, 9, 0, 0).unwrap());
+///
+/// // the last business day of each month at 09:00
+/// let last = schedule.on_business_day(BusinessDay::LAST, days);
+/// let next = last.next_after(&now).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 31, 9, 0, 0).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnBusinessDay<R> {
+    recurrence: R,
+    day: BusinessDay,
+    days: BusinessDays,
+}
+
+impl<R> OnBusinessDay<R> {
+    pub(crate) const fn new(recurrence: R, day: BusinessDay, days: BusinessDays) -> Self {
+        Self {
+            recurrence,
+            day,
+            days,
+        }
+    }
+
+    // the business day in the month of `date`, the first day of that month
+    // and of the next one
+    fn month_of(&self, date: NaiveDate) -> Option<(Option<NaiveDate>, NaiveDate, NaiveDate)> {
+        let first = date.with_day(1)?;
+        let next = first.checked_add_months(Months::new(1))?;
+        Some((self.days.day_in(self.day, date), first, next))
+    }
+}
+
+impl<R: Recurrence> Recurrence for OnBusinessDay<R> {
+    fn first_in<TZ: TimeZone, B: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: B,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let range = bounds(&range);
+        let (Bound::Included(from) | Bound::Excluded(from)) = &range.0 else {
+            return Err(ParseError::InvalidRange);
+        };
+        let tz = from.timezone();
+        let mut date = from.naive_local().date();
+
+        for _ in 0..self.max_months(&range.1) {
+            let Some((day, _, next_month)) = self.month_of(date) else {
+                return Ok(None);
+            };
+            if let Some(day) = day
+                && let Some(window) = window(&tz, day, &range)
+                && let Some(next) = self.recurrence.first_in(window)?
+            {
+                return Ok(Some(next));
+            }
+
+            let Some(next_start) = start_of_day(&tz, next_month) else {
+                return Ok(None);
+            };
+            if after_end(&range.1, &next_start) {
+                return Ok(None);
+            }
+            date = next_month;
+        }
+        Err(ParseError::HorizonExceeded)
+    }
+
+    fn last_in<TZ: TimeZone, B: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: B,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let range = bounds(&range);
+        let (Bound::Included(to) | Bound::Excluded(to)) = &range.1 else {
+            return Err(ParseError::InvalidRange);
+        };
+        let tz = to.timezone();
+        let mut date = to.naive_local().date();
+
+        for _ in 0..self.max_months(&range.0) {
+            let Some((day, first, _)) = self.month_of(date) else {
+                return Ok(None);
+            };
+            if let Some(day) = day
+                && let Some(window) = window(&tz, day, &range)
+                && let Some(prev) = self.recurrence.last_in(window)?
+            {
+                return Ok(Some(prev));
+            }
+
+            let (Some(month_start), Some(prev_month)) =
+                (start_of_day(&tz, first), first.pred_opt())
+            else {
+                return Ok(None);
+            };
+            // the previous month ends before the range
+            let start_reached = match &range.0 {
+                Bound::Included(start) | Bound::Excluded(start) => *start >= month_start,
+                Bound::Unbounded => false,
+            };
+            if start_reached {
+                return Ok(None);
+            }
+            date = prev_month;
+        }
+        Err(ParseError::HorizonExceeded)
+    }
+
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        let date = dt.naive_local().date();
+        self.recurrence.matches(dt) && self.days.day_in(self.day, date) == Some(date)
+    }
+
+    fn horizon(&self) -> Option<u32> {
+        self.recurrence.horizon()
+    }
+}
+
+impl<R: Recurrence> OnBusinessDay<R> {
+    // how many months to search, up to the horizon of the recurrence if the
+    // range doesn't end in the direction of the search
+    fn max_months<TZ: TimeZone>(&self, end: &Bound<DateTime<TZ>>) -> u32 {
+        match (end, self.recurrence.horizon()) {
+            // the month of the start and the months of the horizon
+            (Bound::Unbounded, Some(years)) => years
+                .saturating_mul(12)
+                .saturating_add(1)
+                .min(MAX_SKIPPED_DATES),
+            _ => MAX_SKIPPED_DATES,
+        }
+    }
+}
+
+// helper function returning the part of `range` within the local day `day`
+fn window<TZ: TimeZone>(tz: &TZ, day: NaiveDate, range: &Bounds<TZ>) -> Option<Bounds<TZ>> {
+    let open = start_of_day(tz, day)?;
+    let close = start_of_day(tz, day.succ_opt()?)?;
+    if after_end(&range.1, &open) {
+        return None;
+    }
+    let start = match &range.0 {
+        Bound::Included(start) | Bound::Excluded(start) if *start >= close => return None,
+        Bound::Included(start) | Bound::Excluded(start) if *start >= open => range.0.clone(),
+        _ => Bound::Included(open),
+    };
+
+    let end = match &range.1 {
+        Bound::Included(end) | Bound::Excluded(end) if *end < close => range.1.clone(),
+        _ => Bound::Excluded(close),
+    };
+    Some((start, end))
+}
+
+// helper function checking if `dt` is after the end of a range
+fn after_end<TZ: TimeZone>(end: &Bound<DateTime<TZ>>, dt: &DateTime<TZ>) -> bool {
+    match end {
+        Bound::Included(end) => dt > end,
+        Bound::Excluded(end) => dt >= end,
+        Bound::Unbounded => false,
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{MAX_SKIPPED_DATES, ParseError, Recurrence, Schedule, combine::bounds};
+use alloc::vec::Vec;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone};
+use core::ops::{Bound, RangeBounds, RangeInclusive};
+
+/// Dates a recurrence must not fire at, such as bank holidays or change
+/// freezes, attached with [`Recurrence::excluding`](trait.Recurrence.html#method.excluding)
+///
+/// Days are local days in the timezone of the searched dates. Requires the
+/// `alloc` feature.
+///
+/// Example
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use cron_parser::{Calendar, Recurrence, Schedule};
+///
+/// let day = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+/// let calendar = Calendar::new()
+///     // bank holidays
+///     .exclude_date(day(12, 25))
+///     .exclude_date(day(12, 26))
+///     // change freeze
+///     .exclude_dates(day(12, 27)..=day(12, 31))
+///     // no runs on the first Monday of the month
+///     .exclude_schedule("* * 1-7 * Mon".parse().unwrap());
+///
+/// let schedule: Schedule = "0 9 * * Mon-Fri".parse().unwrap();
+/// let batch = schedule.excluding(calendar);
+///
+/// let now = Utc.with_ymd_and_hms(2024, 12, 24, 10, 0, 0).unwrap();
+/// let next = batch.next_after(&now).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap());
+/// let next = batch.next_after(&next).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2025, 1, 2, 9, 0, 0).unwrap());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Calendar {
+    exclusions: Vec<Exclusion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Exclusion {
+    Days(RangeInclusive<NaiveDate>),
+    Schedule(Schedule),
+}
+
+impl Calendar {
+    /// Create a calendar without exclusions
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            exclusions: Vec::new(),
+        }
+    }
+
+    /// Exclude a whole day
+    #[must_use]
+    pub fn exclude_date(self, date: NaiveDate) -> Self {
+        self.exclude_dates(date..=date)
+    }
+
+    /// Exclude every day from the start to the end of `dates`, both included
+    #[must_use]
+    pub fn exclude_dates(mut self, dates: RangeInclusive<NaiveDate>) -> Self {
+        self.exclusions.push(Exclusion::Days(dates));
+        self
+    }
+
+    /// Exclude the minutes `schedule` matches, e.g. `* 12 * * *` for the hour
+    /// after noon
+    #[must_use]
+    pub fn exclude_schedule(mut self, schedule: Schedule) -> Self {
+        self.exclusions.push(Exclusion::Schedule(schedule));
+        self
+    }
+
+    /// Check if `dt` is excluded, in the timezone of `dt`
+    #[must_use]
+    pub fn excludes<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        let date = dt.naive_local().date();
+        self.exclusions.iter().any(|exclusion| match exclusion {
+            Exclusion::Days(days) => days.contains(&date),
+            Exclusion::Schedule(schedule) => schedule.matches(dt),
+        })
+    }
+
+    // last day of the excluded days containing `date`, to skip them at once
+    fn last_excluded(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.exclusions
+            .iter()
+            .filter_map(|exclusion| match exclusion {
+                Exclusion::Days(days) if days.contains(&date) => Some(*days.end()),
+                _ => None,
+            })
+            .max()
+    }
+
+    // first day of the excluded days containing `date`
+    fn first_excluded(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.exclusions
+            .iter()
+            .filter_map(|exclusion| match exclusion {
+                Exclusion::Days(days) if days.contains(&date) => Some(*days.start()),
+                _ => None,
+            })
+            .min()
+    }
+}
+
+/// Dates of a recurrence the [`Calendar`](struct.Calendar.html) doesn't
+/// exclude, created with [`Recurrence::excluding`](trait.Recurrence.html#method.excluding)
+///
+/// Excluded days are skipped at once, the search fails with
+/// [`ParseError::HorizonExceeded`](enum.ParseError.html) after
+/// [`MAX_SKIPPED_DATES`](constant.MAX_SKIPPED_DATES.html) other excluded dates
+/// in a row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Excluding<R> {
+    recurrence: R,
+    calendar: Calendar,
+}
+
+impl<R> Excluding<R> {
+    pub(crate) const fn new(recurrence: R, calendar: Calendar) -> Self {
+        Self {
+            recurrence,
+            calendar,
+        }
+    }
+
+    /// The exclusions
+    #[must_use]
+    pub const fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+}
+
+impl<R: Recurrence> Recurrence for Excluding<R> {
+    fn first_in<TZ: TimeZone, B: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: B,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (mut start, end) = bounds(&range);
+        for _ in 0..MAX_SKIPPED_DATES {
+            let Some(next) = self.recurrence.first_in((start, end.clone()))? else {
+                return Ok(None);
+            };
+            if !self.calendar.excludes(&next) {
+                return Ok(Some(next));
+            }
+
+            // from the day after the excluded days
+            let after = self
+                .calendar
+                .last_excluded(next.naive_local().date())
+                .and_then(|last| last.succ_opt())
+                .and_then(|day| start_of_day(&next.timezone(), day));
+            start = match after {
+                Some(after) if after > next => Bound::Included(after),
+                _ => Bound::Excluded(next),
+            };
+        }
+        Err(ParseError::HorizonExceeded)
+    }
+
+    fn last_in<TZ: TimeZone, B: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: B,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (start, mut end) = bounds(&range);
+        for _ in 0..MAX_SKIPPED_DATES {
+            let Some(prev) = self.recurrence.last_in((start.clone(), end))? else {
+                return Ok(None);
+            };
+            if !self.calendar.excludes(&prev) {
+                return Ok(Some(prev));
+            }
+
+            // before the first of the excluded days
+            let before = self
+                .calendar
+                .first_excluded(prev.naive_local().date())
+                .and_then(|first| start_of_day(&prev.timezone(), first));
+            end = match before {
+                Some(before) if before < prev => Bound::Excluded(before),
+                _ => Bound::Excluded(prev),
+            };
+        }
+        Err(ParseError::HorizonExceeded)
+    }
+
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        self.recurrence.matches(dt) && !self.calendar.excludes(dt)
+    }
+
+    fn horizon(&self) -> Option<u32> {
+        self.recurrence.horizon()
+    }
+}
+
+// helper function returning the first instant of a local day, 01:00 if a DST
+// change skips midnight, `None` beyond the dates chrono can represent
+pub(crate) fn start_of_day<TZ: TimeZone>(tz: &TZ, date: NaiveDate) -> Option<DateTime<TZ>> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    tz.from_local_datetime(&midnight).earliest().or_else(|| {
+        let one = midnight.checked_add_signed(Duration::hours(1))?;
+        tz.from_local_datetime(&one).earliest()
+    })
+}
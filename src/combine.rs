@@ -0,0 +1,251 @@
+use crate::{ParseError, Recurrence};
+use chrono::{DateTime, TimeZone};
+use core::ops::{Bound, RangeBounds};
+
+/// Most dates skipped by a single search of an
+/// [`Intersection`](struct.Intersection.html), a
+/// [`Difference`](struct.Difference.html) or an `Excluding` before giving up
+/// with
+/// [`ParseError::HorizonExceeded`](enum.ParseError.html), so a combination
+/// that never fires doesn't search forever
+pub const MAX_SKIPPED_DATES: u32 = 100_000;
+
+/// Dates of either recurrence, created with
+/// [`Recurrence::union`](trait.Recurrence.html#method.union)
+///
+/// A date of both is returned once.
+///
+/// Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron_parser::{Recurrence, Schedule};
+///
+/// let mornings: Schedule = "0 9 * * 1-5".parse().unwrap();
+/// let weekends: Schedule = "0 11 * * 0,6".parse().unwrap();
+/// let union = mornings.union(weekends);
+///
+/// // Friday
+/// let now = Utc.with_ymd_and_hms(2024, 1, 5, 10, 0, 0).unwrap();
+/// let next = union.next_after(&now).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 6, 11, 0, 0).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Union<A, B>(A, B);
+
+/// Dates of the first recurrence the second one matches, and the other way
+/// around, created with [`Recurrence::intersection`](trait.Recurrence.html#method.intersection)
+///
+/// The search leapfrogs between both recurrences, it fails with
+/// [`ParseError::HorizonExceeded`](enum.ParseError.html) after
+/// [`MAX_SKIPPED_DATES`](constant.MAX_SKIPPED_DATES.html) dates without a
+/// match.
+///
+/// Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron_parser::{Interval, Recurrence, Schedule};
+///
+/// let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let every_90_minutes = Interval::new(&anchor, Duration::minutes(90)).unwrap();
+/// let business_hours: Schedule = "* 9-17 * * 1-5".parse().unwrap();
+/// let intersection = every_90_minutes.intersection(business_hours);
+///
+/// let next = intersection.next_after(&anchor).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap());
+/// let next = intersection.next_after(&next).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 1, 10, 30, 0).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Intersection<A, B>(A, B);
+
+/// Dates of the first recurrence the second one doesn't match, created with
+/// [`Recurrence::difference`](trait.Recurrence.html#method.difference)
+///
+/// The search fails with [`ParseError::HorizonExceeded`](enum.ParseError.html)
+/// after [`MAX_SKIPPED_DATES`](constant.MAX_SKIPPED_DATES.html) excluded
+/// dates in a row.
+///
+/// Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron_parser::{Recurrence, Schedule};
+///
+/// let business_hours: Schedule = "*/15 9-17 * * 1-5".parse().unwrap();
+/// let lunch: Schedule = "* 12 * * *".parse().unwrap();
+/// let difference = business_hours.difference(lunch);
+///
+/// let now = Utc.with_ymd_and_hms(2024, 1, 5, 11, 50, 0).unwrap();
+/// let next = difference.next_after(&now).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 5, 13, 0, 0).unwrap());
+/// assert!(!difference.matches(&Utc.with_ymd_and_hms(2024, 1, 5, 12, 15, 0).unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Difference<A, B>(A, B);
+
+impl<A, B> Union<A, B> {
+    pub(crate) const fn new(a: A, b: B) -> Self {
+        Self(a, b)
+    }
+}
+
+impl<A, B> Intersection<A, B> {
+    pub(crate) const fn new(a: A, b: B) -> Self {
+        Self(a, b)
+    }
+}
+
+impl<A, B> Difference<A, B> {
+    pub(crate) const fn new(a: A, b: B) -> Self {
+        Self(a, b)
+    }
+}
+
+impl<A: Recurrence, B: Recurrence> Recurrence for Union<A, B> {
+    fn first_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let range = bounds(&range);
+        let a = self.0.first_in(range.clone())?;
+        let b = self.1.first_in(range)?;
+        Ok(match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        })
+    }
+
+    fn last_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let range = bounds(&range);
+        let a = self.0.last_in(range.clone())?;
+        let b = self.1.last_in(range)?;
+        Ok(match (a, b) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        })
+    }
+
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        self.0.matches(dt) || self.1.matches(dt)
+    }
+
+    // either one can still have dates
+    fn horizon(&self) -> Option<u32> {
+        let (a, b) = (self.0.horizon()?, self.1.horizon()?);
+        Some(a.max(b))
+    }
+}
+
+impl<A: Recurrence, B: Recurrence> Recurrence for Intersection<A, B> {
+    fn first_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (mut start, end) = bounds(&range);
+        for _ in 0..MAX_SKIPPED_DATES {
+            let Some(a) = self.0.first_in((start, end.clone()))? else {
+                return Ok(None);
+            };
+            if self.1.matches(&a) {
+                return Ok(Some(a));
+            }
+            let Some(b) = self.1.first_in((Bound::Excluded(a), end.clone()))? else {
+                return Ok(None);
+            };
+            if self.0.matches(&b) {
+                return Ok(Some(b));
+            }
+            start = Bound::Excluded(b);
+        }
+        Err(ParseError::HorizonExceeded)
+    }
+
+    fn last_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (start, mut end) = bounds(&range);
+        for _ in 0..MAX_SKIPPED_DATES {
+            let Some(a) = self.0.last_in((start.clone(), end))? else {
+                return Ok(None);
+            };
+            if self.1.matches(&a) {
+                return Ok(Some(a));
+            }
+            let Some(b) = self.1.last_in((start.clone(), Bound::Excluded(a)))? else {
+                return Ok(None);
+            };
+            if self.0.matches(&b) {
+                return Ok(Some(b));
+            }
+            end = Bound::Excluded(b);
+        }
+        Err(ParseError::HorizonExceeded)
+    }
+
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        self.0.matches(dt) && self.1.matches(dt)
+    }
+
+    // both must still have dates
+    fn horizon(&self) -> Option<u32> {
+        match (self.0.horizon(), self.1.horizon()) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+impl<A: Recurrence, B: Recurrence> Recurrence for Difference<A, B> {
+    fn first_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (mut start, end) = bounds(&range);
+        for _ in 0..MAX_SKIPPED_DATES {
+            let Some(a) = self.0.first_in((start, end.clone()))? else {
+                return Ok(None);
+            };
+            if !self.1.matches(&a) {
+                return Ok(Some(a));
+            }
+            start = Bound::Excluded(a);
+        }
+        Err(ParseError::HorizonExceeded)
+    }
+
+    fn last_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (start, mut end) = bounds(&range);
+        for _ in 0..MAX_SKIPPED_DATES {
+            let Some(a) = self.0.last_in((start.clone(), end))? else {
+                return Ok(None);
+            };
+            if !self.1.matches(&a) {
+                return Ok(Some(a));
+            }
+            end = Bound::Excluded(a);
+        }
+        Err(ParseError::HorizonExceeded)
+    }
+
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        self.0.matches(dt) && !self.1.matches(dt)
+    }
+
+    fn horizon(&self) -> Option<u32> {
+        self.0.horizon()
+    }
+}
+
+// bounds of a range of dates, which can be searched several times
+pub(crate) type Bounds<TZ> = (Bound<DateTime<TZ>>, Bound<DateTime<TZ>>);
+
+// helper function returning the bounds of a range, to search it several times
+pub(crate) fn bounds<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(range: &R) -> Bounds<TZ> {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
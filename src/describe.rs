@@ -0,0 +1,271 @@
+use crate::{
+    Field, FieldSet, Locale, Schedule,
+    field_set::{Term, Terms},
+    locale::{Unit, write_template},
+};
+use core::{fmt, iter};
+
+/// Description of a [`Schedule`](struct.Schedule.html), created with
+/// [`Schedule::describe`](struct.Schedule.html#method.describe) or
+/// [`Schedule::describe_in`](struct.Schedule.html#method.describe_in)
+///
+/// Written when displayed, e.g. with `to_string()`, so describing a schedule
+/// doesn't allocate.
+#[derive(Debug, Clone, Copy)]
+pub struct Description<'a> {
+    schedule: &'a Schedule,
+    locale: &'a Locale,
+}
+
+impl<'a> Description<'a> {
+    pub(crate) const fn new(schedule: &'a Schedule, locale: &'a Locale) -> Self {
+        Self { schedule, locale }
+    }
+
+    // the terms of a field in the words of the locale
+    const fn terms(&self, set: FieldSet, field: Field) -> FieldTerms<'a> {
+        let unit = match field {
+            Field::Minute => &self.locale.minute,
+            Field::Hour => &self.locale.hour,
+            Field::DayOfMonth => &self.locale.day,
+            Field::Month => &self.locale.month,
+            Field::DayOfWeek => &self.locale.weekday,
+        };
+        FieldTerms {
+            set,
+            field,
+            unit,
+            locale: self.locale,
+        }
+    }
+}
+
+impl fmt::Display for Description<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (schedule, locale) = (self.schedule, self.locale);
+        let only = |set: FieldSet, field: Field| Terms::new(set, field).only();
+        let hours_all = only(schedule.hours, Field::Hour) == Some(Term::All);
+
+        // <minute> <hour> * * *
+        let connector = match only(schedule.minutes, Field::Minute) {
+            Some(Term::All) => {
+                f.write_str(locale.every_minute)?;
+                Some(locale.during)
+            }
+            Some(Term::Every(step)) => {
+                write_template(f, locale.every_minutes, &[&step])?;
+                Some(locale.during)
+            }
+            Some(Term::Value(minute))
+                if !hours_all
+                    && Terms::new(schedule.hours, Field::Hour)
+                        .all(|term| matches!(term, Term::Value(_))) =>
+            {
+                let times = List {
+                    items: || schedule.hours.iter().map(|hour| Time(hour, minute)),
+                    and: locale.and,
+                };
+                write_template(f, locale.at_times, &[&times])?;
+                None
+            }
+            _ => {
+                let minutes = self.terms(schedule.minutes, Field::Minute);
+                write_template(f, locale.at_minutes, &[&minutes])?;
+                if hours_all {
+                    f.write_str(locale.past_the_hour)?;
+                }
+                Some(locale.past)
+            }
+        };
+        if let Some(connector) = connector
+            && !hours_all
+        {
+            match only(schedule.hours, Field::Hour) {
+                Some(Term::Value(hour)) => {
+                    write_template(f, locale.between, &[&Time(hour, 0), &Time(hour, 59)])?;
+                }
+                Some(Term::Range(start, last)) => {
+                    write_template(f, locale.between, &[&Time(start, 0), &Time(last, 59)])?;
+                }
+                _ => {
+                    let hours = self.terms(schedule.hours, Field::Hour);
+                    write_template(f, connector, &[&hours])?;
+                }
+            }
+        }
+
+        // * * <dom> * <dow>, both must match
+        let days_of_month = only(schedule.days_of_month, Field::DayOfMonth);
+        if days_of_month != Some(Term::All) {
+            let days = self.terms(schedule.days_of_month, Field::DayOfMonth);
+            write_template(f, locale.days_of_month, &[&days])?;
+        }
+        let days_of_week = self.terms(schedule.days_of_week, Field::DayOfWeek);
+        match (days_of_month, only(schedule.days_of_week, Field::DayOfWeek)) {
+            (_, Some(Term::All)) => {}
+            (Some(Term::All), Some(Term::Range(..))) => {
+                write_template(f, locale.weekday_range, &[&days_of_week])?;
+            }
+            (Some(Term::All), _) => write_template(f, locale.only_on, &[&days_of_week])?,
+            _ => write_template(f, locale.if_on, &[&days_of_week])?,
+        }
+
+        // * * * <month> *
+        if only(schedule.months, Field::Month) != Some(Term::All) {
+            let months = self.terms(schedule.months, Field::Month);
+            write_template(f, locale.only_in, &[&months])?;
+        }
+        Ok(())
+    }
+}
+
+// the terms of a field as a list, the numbers prefixed with the unit, e.g.
+// `minutes 0 and 30` or `every 15th minute and minute 7`
+struct FieldTerms<'a> {
+    set: FieldSet,
+    field: Field,
+    unit: &'a Unit,
+    locale: &'a Locale,
+}
+
+impl FieldTerms<'_> {
+    // names of the values, from the minimum of the field
+    const fn names(&self) -> &[&str] {
+        match self.field {
+            Field::DayOfWeek => &self.locale.weekdays,
+            Field::Month => &self.locale.months,
+            Field::Minute | Field::Hour | Field::DayOfMonth => &[],
+        }
+    }
+}
+
+impl fmt::Display for FieldTerms<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms = || Terms::new(self.set, self.field);
+        let numbers = |term: &Term| {
+            self.names().is_empty() && matches!(term, Term::Value(_) | Term::Range(..))
+        };
+        let items = || {
+            let next = terms().skip(1).map(Some).chain(iter::once(None));
+            terms()
+                .zip(next)
+                .scan(false, move |after_numbers, (term, next)| {
+                    // the unit before the first of consecutive numbers
+                    let several =
+                        matches!(term, Term::Range(..)) || next.is_some_and(|next| numbers(&next));
+                    let noun = (numbers(&term) && !*after_numbers).then_some(if several {
+                        self.unit.plural
+                    } else {
+                        self.unit.singular
+                    });
+                    *after_numbers = numbers(&term);
+                    Some(Phrase(term, noun, self))
+                })
+        };
+
+        let list = List {
+            items,
+            and: self.locale.and,
+        };
+        write!(f, "{list}")
+    }
+}
+
+// phrase for a single term, e.g. `9 through 17` or `every 2nd hour`, after
+// the unit of the numbers
+struct Phrase<'a>(Term, Option<&'a str>, &'a FieldTerms<'a>);
+
+impl fmt::Display for Phrase<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self(term, noun, terms) = *self;
+        if let Some(noun) = noun {
+            write!(f, "{noun} ")?;
+        }
+        let value = |value| Value(value, terms);
+        let ordinal = |number| Ordinal(number, terms.locale);
+        match term {
+            Term::All => {
+                let (min, max) = (terms.field.min(), terms.field.max());
+                write_template(f, terms.locale.range, &[&value(min), &value(max)])
+            }
+            Term::Value(v) => write!(f, "{}", value(v)),
+            Term::Range(start, last) => {
+                write_template(f, terms.locale.range, &[&value(start), &value(last)])
+            }
+            Term::Every(step) => write_template(f, terms.unit.every_nth, &[&ordinal(step)]),
+            Term::Step { start, last, step } => {
+                let every = Template(terms.unit.every_nth, ordinal(step));
+                write_template(f, terms.locale.step, &[&every, &value(start), &value(last)])
+            }
+        }
+    }
+}
+
+// a value of a field, by name if it has one
+struct Value<'a>(u32, &'a FieldTerms<'a>);
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self(value, terms) = *self;
+        let name = value
+            .checked_sub(terms.field.min())
+            .and_then(|index| usize::try_from(index).ok())
+            .and_then(|index| terms.names().get(index));
+        match name {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{value}"),
+        }
+    }
+}
+
+// `a`, `a and b` or `a, b and c`
+struct List<F> {
+    items: F,
+    and: &'static str,
+}
+
+impl<F: Fn() -> I, I: Iterator<Item = T>, T: fmt::Display> fmt::Display for List<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut items = (self.items)().peekable();
+        let mut first = true;
+        while let Some(item) = items.next() {
+            if !first {
+                f.write_str(if items.peek().is_some() {
+                    ", "
+                } else {
+                    self.and
+                })?;
+            }
+            first = false;
+            write!(f, "{item}")?;
+        }
+        Ok(())
+    }
+}
+
+// a template with a single argument
+struct Template<T>(&'static str, T);
+
+impl<T: fmt::Display> fmt::Display for Template<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_template(f, self.0, &[&self.1])
+    }
+}
+
+// `09:30`
+struct Time(u32, u32);
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.0, self.1)
+    }
+}
+
+// a number as an ordinal of the locale
+struct Ordinal<'a>(u32, &'a Locale);
+
+impl fmt::Display for Ordinal<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.1.ordinal)(self.0, f)
+    }
+}
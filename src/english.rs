@@ -0,0 +1,581 @@
+use crate::{Field, FieldSet, ParseError, Schedule};
+
+const WEEKDAYS: [&str; 7] = [
+    "sunday",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+];
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+impl Schedule {
+    /// Build a schedule from a phrase in a small subset of English, e.g.
+    /// `every weekday at 9am` or `every 2 hours on weekends`
+    ///
+    /// Words are case insensitive and commas are ignored. A phrase is a
+    /// sequence of clauses and each field can be set by one clause only. The
+    /// 1st of `every month` and `monthly`, January 1st of `every year` and
+    /// `yearly` and the Sunday of `weekly` are defaults, replaced by `on` and
+    /// `in` clauses:
+    /// * `every minute`, `every 15 minutes`, `every hour`, `every 2 hours`,
+    ///   `every day`, `every 2 days`, `every month`, `every 3 months`,
+    ///   `every year`, `hourly`, `daily`, `weekly`, `monthly`, `yearly` or
+    ///   `annually`
+    /// * `every weekday`, `every weekend`, `every monday and friday`
+    /// * `at 9am`, `at 9:30 pm`, `at 17:45`, `at noon`, `at midnight`,
+    ///   `at 9am and 5pm`, several times must have the same minute
+    /// * `between 9am and 5pm` or `from 9am to 5pm`, whole hours, the end is
+    ///   excluded, `every 2 hours between 9am and 5pm` starts at 9am
+    /// * `on weekdays`, `on weekends`, `on mondays and fridays`,
+    ///   `on monday through friday`, `from monday to friday`
+    /// * `on the 1st and 15th`, `on day 1`, optionally followed by
+    ///   `of the month`
+    /// * `in january`, `in march and april`, `in june through august`
+    ///
+    /// Days of the week and months are their full names or their first three
+    /// letters, days of the week may be plural. Without a time the schedule
+    /// fires at midnight, or every hour after `every minute`,
+    /// `every 15 minutes` and `every hour`.
+    ///
+    /// Example
+    /// ```
+    /// use cron_parser::{ParseError, Schedule};
+    ///
+    /// let schedule = Schedule::from_english("every weekday at 9am").unwrap();
+    /// assert_eq!(schedule.to_string(), "0 9 * * 1-5");
+    ///
+    /// let schedule = Schedule::from_english("every 2 hours on weekends").unwrap();
+    /// assert_eq!(schedule.to_string(), "0 */2 * * 0,6");
+    ///
+    /// let schedule = Schedule::from_english("monthly on the 15th at 9am").unwrap();
+    /// assert_eq!(schedule.to_string(), "0 9 15 * *");
+    ///
+    /// // "fortnight" starts at byte 6
+    /// assert!(matches!(
+    ///     Schedule::from_english("every fortnight"),
+    ///     Err(ParseError::UnsupportedPhrase(6))
+    /// ));
+    /// ```
+    /// # Errors
+    /// [`ParseError::UnsupportedPhrase`](enum.ParseError.html) with the byte
+    /// offset of the first word that isn't understood, or the length of the
+    /// phrase if it ends too early,
+    /// [`ParseError::NeverMatches`](enum.ParseError.html) if the days never
+    /// exist, e.g. `on the 31st in february`.
+    pub fn from_english(phrase: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser {
+            words: Words { phrase, offset: 0 },
+            fields: [None; 5],
+            defaults: [None; 5],
+            every_hour: false,
+            hour_step: None,
+            window: None,
+            wildcard: false,
+        };
+
+        if parser.words.peek().is_none() {
+            return Err(ParseError::UnsupportedPhrase(0));
+        }
+        while let Some((offset, word)) = parser.words.next() {
+            parser.clause(offset, word)?;
+        }
+
+        let [minutes, hours, days_of_month, months, days_of_week] = parser.fields;
+        let [
+            _,
+            _,
+            default_days_of_month,
+            default_months,
+            default_days_of_week,
+        ] = parser.defaults;
+        let every_hour = hours.is_none() && parser.hour_step.is_none() && parser.every_hour;
+        let hours = match (parser.hour_step, parser.window) {
+            // every N hours of the window, from its start
+            (Some(step), Some((start, last))) => wrapping(Field::Hour, start, last)
+                .step_by(usize::try_from(step).unwrap_or(usize::MAX))
+                .collect(),
+            (Some(step), None) => steps(Field::Hour, step),
+            (None, _) if every_hour => all(Field::Hour),
+            (None, _) => hours.unwrap_or_else(|| single(0)),
+        };
+        let hours_star = every_hour || (parser.hour_step.is_some() && parser.window.is_none());
+        Self::from_fields(
+            [
+                minutes.unwrap_or_else(|| single(0)),
+                hours,
+                days_of_month
+                    .or(default_days_of_month)
+                    .unwrap_or_else(|| all(Field::DayOfMonth)),
+                months
+                    .or(default_months)
+                    .unwrap_or_else(|| all(Field::Month)),
+                days_of_week
+                    .or(default_days_of_week)
+                    .unwrap_or_else(|| all(Field::DayOfWeek)),
+            ],
+            parser.wildcard || hours_star,
+        )
+    }
+}
+
+// the words of a phrase with their byte offsets, separated by whitespace or
+// commas
+#[derive(Clone)]
+struct Words<'a> {
+    phrase: &'a str,
+    offset: usize,
+}
+
+impl<'a> Words<'a> {
+    fn peek(&self) -> Option<(usize, &'a str)> {
+        self.clone().next()
+    }
+
+    // consume the next word if it is `word`
+    fn eat(&mut self, word: &str) -> bool {
+        let next = self.peek().is_some_and(|(_, next)| is(next, word));
+        if next {
+            self.next();
+        }
+        next
+    }
+
+    // consume the separator of the next item of a list, `and`, a comma or
+    // both
+    fn and(&mut self) -> bool {
+        let comma = self
+            .phrase
+            .get(self.offset..self.next_offset())
+            .is_some_and(|gap| gap.contains(','));
+        self.eat("and") || comma
+    }
+
+    // offset of the next word, or the length of the phrase after the last one
+    fn next_offset(&self) -> usize {
+        self.peek().map_or(self.phrase.len(), |(offset, _)| offset)
+    }
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let separator = |c: char| c.is_whitespace() || c == ',';
+        let rest = self.phrase.get(self.offset..)?;
+        let start = self.offset + rest.find(|c| !separator(c))?;
+        let word = self.phrase.get(start..)?;
+        let end = start + word.find(separator).unwrap_or(word.len());
+        self.offset = end;
+        Some((start, self.phrase.get(start..end)?))
+    }
+}
+
+struct Parser<'a> {
+    words: Words<'a>,
+    // in expression order, `None` until a clause sets the field
+    fields: [Option<FieldSet>; 5],
+    // values used when no clause sets the field, e.g. the 1st for `monthly`
+    defaults: [Option<FieldSet>; 5],
+    // `every minute`, `every 15 minutes` or `every hour`, every hour unless a
+    // clause sets the hours
+    every_hour: bool,
+    // `every N hours`, within the window if there is one
+    hour_step: Option<u32>,
+    // `between 9am and 5pm`, the first and the last hour
+    window: Option<(u32, u32)>,
+    // a clause set the minutes to `*` or `*/step`, a wildcard job for
+    // `with_vixie_dst`
+    wildcard: bool,
+}
+
+impl Parser<'_> {
+    // a clause starting with `word`, at `offset`
+    fn clause(&mut self, offset: usize, word: &str) -> Result<(), ParseError> {
+        if is(word, "every") {
+            self.every(offset)
+        } else if is(word, "at") {
+            self.at(offset)
+        } else if is(word, "between") || is(word, "from") {
+            self.between(offset)
+        } else if is(word, "on") {
+            self.on(offset)
+        } else if is(word, "in") {
+            let months = self.list(Field::Month)?;
+            self.set(offset, Field::Month, months)
+        } else if is(word, "hourly") {
+            self.wildcard = true;
+            self.set(offset, Field::Minute, single(0))?;
+            self.set(offset, Field::Hour, all(Field::Hour))
+        } else if is(word, "daily") {
+            Ok(())
+        } else if is(word, "weekly") {
+            self.default(offset, Field::DayOfWeek, single(0))
+        } else if is(word, "monthly") {
+            self.default(offset, Field::DayOfMonth, single(1))
+        } else if is(word, "yearly") || is(word, "annually") {
+            self.default(offset, Field::DayOfMonth, single(1))?;
+            self.default(offset, Field::Month, single(1))
+        } else {
+            Err(ParseError::UnsupportedPhrase(offset))
+        }
+    }
+
+    // every [N] minute(s), hour(s), day(s), month(s), year, or days of the
+    // week
+    fn every(&mut self, offset: usize) -> Result<(), ParseError> {
+        let step_offset = self.words.next_offset();
+        let step = self
+            .words
+            .peek()
+            .and_then(|(_, word)| word.parse::<u32>().ok());
+        if step.is_some() {
+            self.words.next();
+        }
+        let unit_offset = self.words.next_offset();
+        let unit = self.words.peek().map_or("", |(_, word)| word);
+        let plural = step.is_some();
+        let unit_is = |singular: &str| {
+            if plural {
+                unit.len() == singular.len() + 1
+                    && unit
+                        .get(..singular.len())
+                        .is_some_and(|stem| is(stem, singular))
+                    && unit.ends_with(['s', 'S'])
+            } else {
+                is(unit, singular)
+            }
+        };
+        let every = |field: Field| match step {
+            Some(step) if (1..=field.max()).contains(&step) => Ok(steps(field, step)),
+            Some(_) => Err(ParseError::UnsupportedPhrase(step_offset)),
+            None => Ok(all(field)),
+        };
+
+        if unit_is("minute") {
+            self.every_hour = true;
+            self.wildcard = true;
+            self.set(offset, Field::Minute, every(Field::Minute)?)?;
+        } else if unit_is("hour") {
+            self.set(offset, Field::Minute, single(0))?;
+            if let Some(step) = step {
+                every(Field::Hour)?;
+                if self
+                    .fields
+                    .get(1)
+                    .is_some_and(|hours| hours.is_some() && self.window.is_none())
+                {
+                    return Err(ParseError::UnsupportedPhrase(offset));
+                }
+                self.hour_step = Some(step);
+            } else {
+                self.every_hour = true;
+            }
+        } else if unit_is("day") {
+            if plural {
+                self.set(offset, Field::DayOfMonth, every(Field::DayOfMonth)?)?;
+            }
+        } else if unit_is("month") {
+            self.default(offset, Field::DayOfMonth, single(1))?;
+            self.set(offset, Field::Month, every(Field::Month)?)?;
+        } else if !plural && unit_is("year") {
+            self.default(offset, Field::DayOfMonth, single(1))?;
+            self.default(offset, Field::Month, single(1))?;
+        } else if !plural {
+            let days = self.days_of_week()?;
+            return self.set(offset, Field::DayOfWeek, days);
+        } else {
+            return Err(ParseError::UnsupportedPhrase(unit_offset));
+        }
+        self.words.next();
+        Ok(())
+    }
+
+    // at 9am, at 9:30 and 17:30
+    fn at(&mut self, offset: usize) -> Result<(), ParseError> {
+        let (hour, minute) = self.time()?;
+        let mut hours = single(hour);
+        while self.words.and() {
+            let next = self.words.next_offset();
+            let (hour, other) = self.time()?;
+            if other != minute {
+                return Err(ParseError::UnsupportedPhrase(next));
+            }
+            hours.insert(hour);
+        }
+        self.set(offset, Field::Minute, single(minute))?;
+        self.set(offset, Field::Hour, hours)
+    }
+
+    // between 9am and 5pm, from monday to friday
+    fn between(&mut self, offset: usize) -> Result<(), ParseError> {
+        let weekday = self
+            .words
+            .peek()
+            .is_some_and(|(_, word)| day_of_week(word).is_some());
+        if weekday {
+            let start = self.value(Field::DayOfWeek)?;
+            let separator = self.words.next_offset();
+            if !(self.words.eat("to") || self.words.eat("through") || self.words.eat("and")) {
+                return Err(ParseError::UnsupportedPhrase(separator));
+            }
+            let last = self.value(Field::DayOfWeek)?;
+            return self.set(
+                offset,
+                Field::DayOfWeek,
+                wrapping(Field::DayOfWeek, start, last).collect(),
+            );
+        }
+
+        let start_offset = self.words.next_offset();
+        let (start, start_minute) = self.time()?;
+        if start_minute != 0 {
+            return Err(ParseError::UnsupportedPhrase(start_offset));
+        }
+        let separator = self.words.next_offset();
+        if !(self.words.eat("and") || self.words.eat("to")) {
+            return Err(ParseError::UnsupportedPhrase(separator));
+        }
+        let end_offset = self.words.next_offset();
+        let (end, end_minute) = self.time()?;
+        if end_minute != 0 || end == start {
+            return Err(ParseError::UnsupportedPhrase(end_offset));
+        }
+        let last = end.checked_sub(1).unwrap_or(Field::Hour.max());
+        self.window = Some((start, last));
+        self.set(
+            offset,
+            Field::Hour,
+            wrapping(Field::Hour, start, last).collect(),
+        )
+    }
+
+    // on weekdays, on mondays and fridays, on the 1st and 15th of the month
+    fn on(&mut self, offset: usize) -> Result<(), ParseError> {
+        if !(self.words.eat("the") || self.words.eat("day")) {
+            let days = self.days_of_week()?;
+            return self.set(offset, Field::DayOfWeek, days);
+        }
+
+        let days = self.list(Field::DayOfMonth)?;
+        if self.words.eat("of") {
+            let end = self.words.next_offset();
+            if !(self.words.eat("the") && self.words.eat("month")) {
+                return Err(ParseError::UnsupportedPhrase(end));
+            }
+        }
+        self.set(offset, Field::DayOfMonth, days)
+    }
+
+    // weekdays, weekends or a list of days of the week
+    fn days_of_week(&mut self) -> Result<FieldSet, ParseError> {
+        if self.words.eat("weekday") || self.words.eat("weekdays") {
+            Ok((1..=5).collect())
+        } else if self.words.eat("weekend") || self.words.eat("weekends") {
+            Ok([0, 6].into_iter().collect())
+        } else {
+            self.list(Field::DayOfWeek)
+        }
+    }
+
+    // `a`, `a and b`, `a, b and c`, where each item is a value of `field` or a
+    // range such as `a through b`
+    fn list(&mut self, field: Field) -> Result<FieldSet, ParseError> {
+        let mut set = FieldSet::new();
+        loop {
+            let start = self.value(field)?;
+            let last = if self.words.eat("through") || self.words.eat("to") {
+                self.value(field)?
+            } else {
+                start
+            };
+            set = set.iter().chain(wrapping(field, start, last)).collect();
+            if !self.words.and() {
+                return Ok(set);
+            }
+        }
+    }
+
+    // a day of the month, a month or a day of the week
+    fn value(&mut self, field: Field) -> Result<u32, ParseError> {
+        let offset = self.words.next_offset();
+        self.words
+            .next()
+            .and_then(|(_, word)| match field {
+                Field::DayOfMonth => day_of_month(word),
+                Field::Month => month(word),
+                Field::DayOfWeek => day_of_week(word),
+                Field::Minute | Field::Hour => None,
+            })
+            .ok_or(ParseError::UnsupportedPhrase(offset))
+    }
+
+    // 9am, 9 am, 9:30pm, 17:45, noon or midnight, as an hour and a minute
+    fn time(&mut self) -> Result<(u32, u32), ParseError> {
+        let unsupported = ParseError::UnsupportedPhrase(self.words.next_offset());
+        let Some((_, word)) = self.words.next() else {
+            return Err(unsupported);
+        };
+        if is(word, "noon") {
+            return Ok((12, 0));
+        }
+        if is(word, "midnight") {
+            return Ok((0, 0));
+        }
+
+        let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let mut suffix = word.get(digits.len()..).unwrap_or_default();
+        if suffix.is_empty()
+            && let Some((_, next)) = self.words.peek()
+            && (is(next, "am") || is(next, "pm"))
+        {
+            self.words.next();
+            suffix = next;
+        }
+
+        let (hour, minute) = match digits.split_once(':') {
+            Some((hour, minute)) if minute.len() == 2 => (hour, minute),
+            Some(_) => return Err(unsupported),
+            None => (digits, "0"),
+        };
+        let (Ok(hour), Ok(minute)) = (hour.parse::<u32>(), minute.parse::<u32>()) else {
+            return Err(unsupported);
+        };
+        let hour = if suffix.is_empty() {
+            hour
+        } else if !(1..=12).contains(&hour) {
+            return Err(unsupported);
+        } else if is(suffix, "am") {
+            hour % 12
+        } else if is(suffix, "pm") {
+            hour % 12 + 12
+        } else {
+            return Err(unsupported);
+        };
+        if hour > Field::Hour.max() || minute > Field::Minute.max() {
+            return Err(unsupported);
+        }
+        Ok((hour, minute))
+    }
+
+    // set a field once, the clause at `offset` is unsupported if another
+    // clause already set it
+    fn set(&mut self, offset: usize, field: Field, set: FieldSet) -> Result<(), ParseError> {
+        if field == Field::Hour && self.hour_step.is_some() && self.window.is_none() {
+            return Err(ParseError::UnsupportedPhrase(offset));
+        }
+        set_once(&mut self.fields, offset, field, set)
+    }
+
+    // set the default of a field once
+    fn default(&mut self, offset: usize, field: Field, set: FieldSet) -> Result<(), ParseError> {
+        set_once(&mut self.defaults, offset, field, set)
+    }
+}
+
+// helper function setting a field once, the clause at `offset` is
+// unsupported if the field is already set
+fn set_once(
+    fields: &mut [Option<FieldSet>; 5],
+    offset: usize,
+    field: Field,
+    set: FieldSet,
+) -> Result<(), ParseError> {
+    let [minutes, hours, days_of_month, months, days_of_week] = fields;
+    let current = match field {
+        Field::Minute => minutes,
+        Field::Hour => hours,
+        Field::DayOfMonth => days_of_month,
+        Field::Month => months,
+        Field::DayOfWeek => days_of_week,
+    };
+    if current.is_some() {
+        return Err(ParseError::UnsupportedPhrase(offset));
+    }
+    *current = Some(set);
+    Ok(())
+}
+
+// helper function comparing words, ignoring ASCII case
+fn is(word: &str, expected: &str) -> bool {
+    word.eq_ignore_ascii_case(expected)
+}
+
+fn single(value: u32) -> FieldSet {
+    [value].into_iter().collect()
+}
+
+fn all(field: Field) -> FieldSet {
+    (field.min()..=field.max()).collect()
+}
+
+// `*/step`
+fn steps(field: Field, step: u32) -> FieldSet {
+    (field.min()..=field.max())
+        .step_by(usize::try_from(step).unwrap_or(usize::MAX))
+        .collect()
+}
+
+// `start` through `last`, wrapping around the end of the field, e.g. from
+// Friday to Monday
+fn wrapping(field: Field, start: u32, last: u32) -> impl Iterator<Item = u32> {
+    let (end, wrapped) = if start <= last {
+        (last, None)
+    } else {
+        (field.max(), Some(field.min()..=last))
+    };
+    (start..=end).chain(wrapped.into_iter().flatten())
+}
+
+// `1st`, `2nd`, `15th` or `15`
+fn day_of_month(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = word.get(digits.len()..)?;
+    let day = digits.parse::<u32>().ok()?;
+    let valid = ["", "st", "nd", "rd", "th"]
+        .iter()
+        .any(|expected| is(suffix, expected));
+    (valid && (Field::DayOfMonth.min()..=Field::DayOfMonth.max()).contains(&day)).then_some(day)
+}
+
+// `january` or `jan`, from 1
+fn month(word: &str) -> Option<u32> {
+    name(&MONTHS, word).map(|index| index + 1)
+}
+
+// `monday`, `mondays` or `mon`, from 0 for Sunday
+fn day_of_week(word: &str) -> Option<u32> {
+    name(&WEEKDAYS, word).or_else(|| {
+        let singular = word.strip_suffix(['s', 'S'])?;
+        WEEKDAYS
+            .iter()
+            .position(|name| is(singular, name))
+            .and_then(|index| u32::try_from(index).ok())
+    })
+}
+
+// index of a full name or of its first three letters
+fn name(names: &[&str], word: &str) -> Option<u32> {
+    names
+        .iter()
+        .position(|name| {
+            is(word, name) || (word.len() == 3 && name.get(..3).is_some_and(|abbr| is(word, abbr)))
+        })
+        .and_then(|index| u32::try_from(index).ok())
+}
@@ -0,0 +1,481 @@
+use crate::{Field, ParseError, parse_field_values};
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeSet;
+use core::fmt;
+
+/// Compact set of the values of a cron field, stored as a 64 bit mask
+///
+/// Membership and finding the next value are constant time and parsing
+/// doesn't allocate, values must be in the range 0 - 63 which covers every
+/// cron field.
+///
+/// Example
+/// ```
+/// use cron_parser::FieldSet;
+/// use std::collections::BTreeSet;
+///
+/// let hours = FieldSet::parse("12-18/3", 0, 23).unwrap();
+/// assert!(hours.contains(15));
+/// assert_eq!(hours.next_from(16), Some(18));
+/// assert_eq!(hours.next_from(19), None);
+///
+/// // same values as `parse_field`
+/// assert_eq!(BTreeSet::from(hours), BTreeSet::from([12, 15, 18]));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FieldSet(u64);
+
+impl FieldSet {
+    /// Largest value a `FieldSet` can hold
+    pub const MAX: u32 = u64::BITS - 1;
+
+    /// Create an empty set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Parse a cron field, same syntax as [`parse_field`](fn.parse_field.html)
+    ///
+    /// # Errors
+    /// [`ParseError`](enum.ParseError.html), `InvalidRange` if `max` is
+    /// greater than [`FieldSet::MAX`](#associatedconstant.MAX)
+    pub fn parse(field: &str, min: u32, max: u32) -> Result<Self, ParseError> {
+        if max > Self::MAX {
+            return Err(ParseError::InvalidRange);
+        }
+
+        let mut set = Self::new();
+        parse_field_values(field, min, max, |value| set.insert(value))?;
+        Ok(set)
+    }
+
+    /// Add a value, values greater than [`FieldSet::MAX`](#associatedconstant.MAX) are ignored
+    pub const fn insert(&mut self, value: u32) {
+        if value <= Self::MAX {
+            self.0 |= 1 << value;
+        }
+    }
+
+    /// Remove a value
+    pub const fn remove(&mut self, value: u32) {
+        if value <= Self::MAX {
+            self.0 &= !(1 << value);
+        }
+    }
+
+    /// Check if the set contains `value`
+    #[must_use]
+    pub const fn contains(self, value: u32) -> bool {
+        value <= Self::MAX && self.0 & (1 << value) != 0
+    }
+
+    /// Smallest value in the set greater than or equal to `value`
+    #[must_use]
+    pub const fn next_from(self, value: u32) -> Option<u32> {
+        if value > Self::MAX {
+            return None;
+        }
+
+        let masked = self.0 & (u64::MAX << value);
+        if masked == 0 {
+            None
+        } else {
+            Some(masked.trailing_zeros())
+        }
+    }
+
+    /// Largest value in the set less than or equal to `value`
+    #[must_use]
+    pub const fn prev_from(self, value: u32) -> Option<u32> {
+        let masked = if value >= Self::MAX {
+            self.0
+        } else {
+            self.0 & !(u64::MAX << (value + 1))
+        };
+        if masked == 0 {
+            None
+        } else {
+            Some(Self::MAX - masked.leading_zeros())
+        }
+    }
+
+    /// Smallest value in the set
+    #[must_use]
+    pub const fn first(self) -> Option<u32> {
+        self.next_from(0)
+    }
+
+    /// Largest value in the set
+    #[must_use]
+    pub const fn last(self) -> Option<u32> {
+        self.prev_from(Self::MAX)
+    }
+
+    /// Number of values in the set
+    #[must_use]
+    pub const fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Check if the set has no values
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the values in ascending order
+    #[must_use]
+    pub const fn iter(self) -> FieldSetIter {
+        FieldSetIter(self.0)
+    }
+}
+
+impl fmt::Debug for FieldSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<u32> for FieldSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl IntoIterator for FieldSet {
+    type Item = u32;
+    type IntoIter = FieldSetIter;
+
+    fn into_iter(self) -> FieldSetIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<FieldSet> for BTreeSet<u32> {
+    fn from(set: FieldSet) -> Self {
+        set.iter().collect()
+    }
+}
+
+// term of the canonical expression of a field, contiguous runs become ranges
+// and arithmetic sequences steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Term {
+    // `*`
+    All,
+    // `5`
+    Value(u32),
+    // `1-5`
+    Range(u32, u32),
+    // `*/15`, from the first value of the field until its end
+    Every(u32),
+    // `10-20/5`
+    Step { start: u32, last: u32, step: u32 },
+}
+
+// most steps of the search for the shortest terms of a field, the search for
+// an irregular set of values stops there and keeps the shortest terms found
+const MAX_SEARCH_STEPS: u32 = 2_000;
+
+// iterator over the terms of the canonical expression of a field, disjoint
+// terms sorted by their first value and written with the fewest characters
+#[derive(Debug, Clone)]
+pub(crate) struct Terms {
+    // values of each term
+    values: [FieldSet; 64],
+    len: usize,
+    next: usize,
+    field: Field,
+}
+
+impl Terms {
+    pub(crate) fn new(set: FieldSet, field: Field) -> Self {
+        Self::search(set, field, true)
+    }
+
+    // terms for an expression that doesn't start with `*`, where `*/15` is
+    // written `0-45/15`
+    pub(crate) fn without_star(set: FieldSet, field: Field) -> Self {
+        Self::search(set, field, false)
+    }
+
+    fn search(set: FieldSet, field: Field, star: bool) -> Self {
+        if set == (field.min()..=field.max()).collect() {
+            return Self {
+                values: [set; 64],
+                len: 1,
+                next: 0,
+                field,
+            };
+        }
+
+        let mut search = Search::new(set, field, star);
+        search.visit(set, 0, (0, 0, 0));
+        Self {
+            values: search.best,
+            len: search.best_len,
+            next: 0,
+            field,
+        }
+    }
+
+    // terms starting with `*` or `*/step`, the smallest step whose values are
+    // all in the set, `None` without such a step
+    pub(crate) fn starting_with_every(set: FieldSet, field: Field) -> Option<Self> {
+        let (min, max) = (field.min(), field.max());
+        let every = (1..=max).find_map(|step| {
+            let every: FieldSet = (min..=max).step_by(step as usize).collect();
+            (every.0 & !set.0 == 0).then_some(every)
+        })?;
+
+        let mut search = Search::new(set, field, true);
+        if let Some(first) = search.path.first_mut() {
+            *first = every;
+        }
+        let written = term_of(every, field).map_or(0, |term| written_len(term, field, true));
+        search.visit(FieldSet(set.0 & !every.0), 1, (written, 0, 1));
+        Some(Self {
+            values: search.best,
+            len: search.best_len,
+            next: 0,
+            field,
+        })
+    }
+
+    // the only term of the field, if there is exactly one
+    pub(crate) fn only(mut self) -> Option<Term> {
+        match (self.next(), self.next()) {
+            (Some(term), None) => Some(term),
+            _ => None,
+        }
+    }
+}
+
+impl Iterator for Terms {
+    type Item = Term;
+
+    fn next(&mut self) -> Option<Term> {
+        if self.next >= self.len {
+            return None;
+        }
+        let values = *self.values.get(self.next)?;
+        self.next += 1;
+        term_of(values, self.field)
+    }
+}
+
+// helper function returning the term writing `values`, a single value, a
+// range or a step
+fn term_of(values: FieldSet, field: Field) -> Option<Term> {
+    let (min, max) = (field.min(), field.max());
+    if values == (min..=max).collect() {
+        return Some(Term::All);
+    }
+    let start = values.first()?;
+    let last = values.last()?;
+    let Some(second) = values.next_from(start + 1) else {
+        return Some(Term::Value(start));
+    };
+    let step = second - start;
+    Some(if step == 1 {
+        Term::Range(start, last)
+    } else if start == min && last + step > max {
+        Term::Every(step)
+    } else {
+        Term::Step { start, last, step }
+    })
+}
+
+// helper function returning how many characters a term is written with,
+// `*/step` is written from the minimum of the field without `star`
+fn written_len(term: Term, field: Field, star: bool) -> u32 {
+    let digits = |value: u32| if value >= 10 { 2 } else { 1 };
+    let (min, max) = (field.min(), field.max());
+    match term {
+        Term::All if star => 1,
+        Term::All => digits(min) + 1 + digits(max),
+        Term::Value(value) => digits(value),
+        Term::Range(start, last) => digits(start) + 1 + digits(last),
+        Term::Every(step) if star => 2 + digits(step),
+        Term::Every(step) => digits(min) + 1 + digits(max - (max - min) % step) + 1 + digits(step),
+        Term::Step { start, last, step } => digits(start) + 1 + digits(last) + 1 + digits(step),
+    }
+}
+
+// how good terms are: the fewest characters, on a tie the fewest steps so a
+// step is only written when it is shorter than the values, then the fewest
+// terms
+type Cost = (u32, usize, usize);
+
+// depth-first search of the disjoint terms written with the fewest
+// characters, trying the terms covering the most values first
+struct Search {
+    field: Field,
+    // whether `*` and `*/step` can be written
+    star: bool,
+    // number of values of the longest term, to stop searching branches that
+    // can't beat the best terms
+    longest: u32,
+    path: [FieldSet; 64],
+    best: [FieldSet; 64],
+    best_len: usize,
+    best_cost: Cost,
+    steps: u32,
+}
+
+impl Search {
+    fn new(set: FieldSet, field: Field, star: bool) -> Self {
+        let longest = set
+            .iter()
+            .flat_map(|value| (1..=field.max()).map(move |step| run(set, field, value, step)))
+            .max()
+            .unwrap_or(1);
+        Self {
+            field,
+            star,
+            longest,
+            path: [FieldSet::new(); 64],
+            best: [FieldSet::new(); 64],
+            best_len: 0,
+            best_cost: (u32::MAX, usize::MAX, usize::MAX),
+            steps: 0,
+        }
+    }
+
+    fn visit(&mut self, uncovered: FieldSet, depth: usize, cost: Cost) {
+        let Some(value) = uncovered.first() else {
+            if cost < self.best_cost {
+                self.best = self.path;
+                self.best_len = depth;
+                self.best_cost = cost;
+            }
+            return;
+        };
+        // each term left is at least a comma and a digit
+        let needed = uncovered.len().div_ceil(self.longest);
+        if self.steps >= MAX_SEARCH_STEPS || cost.0 + 2 * needed > self.best_cost.0 {
+            return;
+        }
+        self.steps += 1;
+
+        // the terms starting at the first uncovered value, a range or a step
+        // of at least 3 values, or the value alone
+        let max = self.field.max();
+        let most = (1..=max)
+            .map(|step| run(uncovered, self.field, value, step))
+            .max()
+            .unwrap_or(1);
+        for len in (3..=most).rev() {
+            for step in 1..=max {
+                if run(uncovered, self.field, value, step) >= len {
+                    let term = (value..)
+                        .step_by(step as usize)
+                        .take(len as usize)
+                        .collect();
+                    self.try_term(term, uncovered, depth, cost);
+                }
+            }
+        }
+        let mut single = FieldSet::new();
+        single.insert(value);
+        self.try_term(single, uncovered, depth, cost);
+    }
+
+    // continue the search with `term` after the terms of `path`
+    fn try_term(&mut self, term: FieldSet, uncovered: FieldSet, depth: usize, cost: Cost) {
+        let Some(written) = term_of(term, self.field) else {
+            return;
+        };
+        let (length, steps, terms) = cost;
+        let stepped = matches!(written, Term::Every(_) | Term::Step { .. });
+        let cost = (
+            length + u32::from(depth > 0) + written_len(written, self.field, self.star),
+            steps + usize::from(stepped),
+            terms + 1,
+        );
+        if let Some(slot) = self.path.get_mut(depth) {
+            *slot = term;
+            self.visit(FieldSet(uncovered.0 & !term.0), depth + 1, cost);
+        }
+    }
+}
+
+// helper function returning how many values of `set` follow each other from
+// `value` with `step` between them
+fn run(set: FieldSet, field: Field, value: u32, step: u32) -> u32 {
+    let count = (value..=field.max())
+        .step_by(step as usize)
+        .take_while(|next| set.contains(*next))
+        .count();
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
+// whether the expression of a field starts with `*`, which Vixie cron
+// checks in the minute and hour fields to tell wildcard jobs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Star {
+    // when it is the canonical expression
+    Allowed,
+    // `0-59` instead of `*` and `0-45/15` instead of `*/15`
+    Never,
+    // `*` or `*/step` first, if the values allow it
+    First,
+}
+
+// canonical expression of the values of a field, e.g. `*/15` or `1-5,10-20/5`
+pub(crate) struct FieldDisplay(pub(crate) FieldSet, pub(crate) Field, pub(crate) Star);
+
+impl fmt::Display for FieldDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Self(set, field, star) = *self;
+        let terms = match star {
+            Star::First => Terms::starting_with_every(set, field),
+            Star::Allowed => None,
+            Star::Never => Some(Terms::without_star(set, field)),
+        };
+        let (min, max) = (field.min(), field.max());
+        let mut separator = "";
+        for term in terms.unwrap_or_else(|| Terms::new(set, field)) {
+            f.write_str(separator)?;
+            separator = ",";
+            match term {
+                Term::All if star == Star::Never => write!(f, "{min}-{max}")?,
+                Term::All => f.write_str("*")?,
+                Term::Value(value) => write!(f, "{value}")?,
+                Term::Range(start, last) => write!(f, "{start}-{last}")?,
+                Term::Every(step) if star == Star::Never => {
+                    let last = max - (max - min) % step;
+                    write!(f, "{min}-{last}/{step}")?;
+                }
+                Term::Every(step) => write!(f, "*/{step}")?,
+                Term::Step { start, last, step } => write!(f, "{start}-{last}/{step}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the values of a [`FieldSet`](struct.FieldSet.html)
+#[derive(Debug, Clone)]
+pub struct FieldSetIter(u64);
+
+impl Iterator for FieldSetIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let value = self.0.trailing_zeros();
+        // clear the lowest set bit
+        self.0 &= self.0 - 1;
+        Some(value)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{ParseError, Recurrence};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use core::ops::{Bound, RangeBounds};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Dates a fixed amount of elapsed time apart, e.g. every 90 minutes or
+/// every 36 hours, which cron can't express
+///
+/// The dates are the anchor plus or minus any multiple of the period. They
+/// are instants, unlike cron dates they don't follow local time so DST
+/// changes don't affect them.
+///
+/// Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron_parser::{Interval, Recurrence};
+///
+/// let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let interval = Interval::new(&anchor, Duration::hours(36)).unwrap();
+///
+/// let now = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+/// let next = interval.next_after(&now).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap());
+/// let prev = interval.prev_before(&now).unwrap();
+/// assert_eq!(prev, anchor);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    anchor: DateTime<Utc>,
+    period: Duration,
+}
+
+impl Interval {
+    /// Create an interval of `period` going through `anchor`
+    ///
+    /// # Errors
+    /// [`ParseError::InvalidRange`](enum.ParseError.html) if `period` is not
+    /// positive
+    pub fn new<TZ: TimeZone>(anchor: &DateTime<TZ>, period: Duration) -> Result<Self, ParseError> {
+        if period <= Duration::zero() {
+            return Err(ParseError::InvalidRange);
+        }
+
+        Ok(Self {
+            anchor: anchor.with_timezone(&Utc),
+            period,
+        })
+    }
+
+    /// The date the interval goes through
+    #[must_use]
+    pub const fn anchor(&self) -> DateTime<Utc> {
+        self.anchor
+    }
+
+    /// The time between two dates
+    #[must_use]
+    pub const fn period(&self) -> Duration {
+        self.period
+    }
+
+    // number of periods from the anchor to `dt`, rounded down, and whether
+    // `dt` is exactly on a date
+    fn periods_to<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> (i128, bool) {
+        let elapsed = nanos(dt.with_timezone(&Utc).signed_duration_since(self.anchor));
+        let period = nanos(self.period);
+        (elapsed.div_euclid(period), elapsed.rem_euclid(period) == 0)
+    }
+
+    // the date `periods` periods from the anchor, `None` beyond the dates
+    // chrono can represent
+    fn date<TZ: TimeZone>(&self, tz: &TZ, periods: i128) -> Option<DateTime<TZ>> {
+        let offset = periods.checked_mul(nanos(self.period))?;
+        let seconds = i64::try_from(offset.div_euclid(NANOS_PER_SECOND)).ok()?;
+        let nanoseconds = i64::try_from(offset.rem_euclid(NANOS_PER_SECOND)).ok()?;
+        let offset =
+            Duration::try_seconds(seconds)?.checked_add(&Duration::nanoseconds(nanoseconds))?;
+        let date = self.anchor.checked_add_signed(offset)?;
+        Some(date.with_timezone(tz))
+    }
+}
+
+impl Recurrence for Interval {
+    fn first_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (from, periods) = match range.start_bound() {
+            Bound::Included(start) => match self.periods_to(start) {
+                (periods, true) => (start, periods),
+                (periods, false) => (start, periods + 1),
+            },
+            Bound::Excluded(start) => (start, self.periods_to(start).0 + 1),
+            Bound::Unbounded => return Err(ParseError::InvalidRange),
+        };
+
+        Ok(self
+            .date(&from.timezone(), periods)
+            .filter(|next| range.contains(next)))
+    }
+
+    fn last_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (from, periods) = match range.end_bound() {
+            Bound::Included(end) => (end, self.periods_to(end).0),
+            Bound::Excluded(end) => match self.periods_to(end) {
+                (periods, true) => (end, periods - 1),
+                (periods, false) => (end, periods),
+            },
+            Bound::Unbounded => return Err(ParseError::InvalidRange),
+        };
+
+        Ok(self
+            .date(&from.timezone(), periods)
+            .filter(|prev| range.contains(prev)))
+    }
+
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        self.periods_to(dt).1
+    }
+}
+
+// helper function returning a duration in nanoseconds, without overflowing
+fn nanos(duration: Duration) -> i128 {
+    i128::from(duration.num_seconds()) * NANOS_PER_SECOND + i128::from(duration.subsec_nanos())
+}
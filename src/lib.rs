@@ -27,10 +27,68 @@
 //! // use custom timezone
 //! assert!(parse("*/5 * * * *", &Utc::now().with_timezone(&Lisbon)).is_ok());
 //! ```
-use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
-use std::{collections::BTreeSet, error::Error, fmt, num, str::FromStr};
+//!
+//! Features:
+//! * `std` (default): implies `alloc`.
+//! * `alloc`: [`parse_field`](fn.parse_field.html), reporting every
+//!   invalid field in [`ParseError::InvalidFields`](enum.ParseError.html),
+//!   exclusion [`Calendar`](struct.Calendar.html)s and
+//!   [`BusinessDays`](struct.BusinessDays.html).
+//!
+//! Without default features the crate is `no_std` and doesn't allocate, the
+//! parser and the search only use [`FieldSet`](struct.FieldSet.html) and
+//! calendar arithmetic.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeSet, vec::Vec};
+use chrono::{DateTime, TimeZone, Utc};
+use core::{error::Error, fmt, num, str::FromStr};
+
+mod builder;
+pub use self::builder::{IntoWeekdays, ScheduleBuilder};
+
+mod bounded;
+pub use self::bounded::Bounded;
+
+#[cfg(feature = "alloc")]
+mod business;
+#[cfg(feature = "alloc")]
+pub use self::business::{BusinessDay, BusinessDays, OnBusinessDay};
+
+#[cfg(feature = "alloc")]
+mod calendar;
+#[cfg(feature = "alloc")]
+pub use self::calendar::{Calendar, Excluding};
+
+mod combine;
+pub use self::combine::{Difference, Intersection, MAX_SKIPPED_DATES, Union};
+
+mod describe;
+pub use self::describe::Description;
+
+mod locale;
+pub use self::locale::{Locale, Unit};
+
+mod english;
+
+mod field_set;
+pub use self::field_set::{FieldSet, FieldSetIter};
+
+mod interval;
+pub use self::interval::Interval;
+
+mod recurrence;
+pub use self::recurrence::{Occurrences, Recurrence};
+
+mod schedule;
+pub use self::schedule::{GapPolicy, OverlapPolicy, Schedule};
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ParseError {
     InvalidCron,
     InvalidRange,
@@ -38,6 +96,85 @@ pub enum ParseError {
     ParseIntError(num::ParseIntError),
     TryFromIntError(num::TryFromIntError),
     InvalidTimezone,
+    #[cfg(feature = "alloc")]
+    InvalidFields(Vec<FieldError>),
+    NeverMatches,
+    HorizonExceeded,
+    UnsupportedPhrase(usize),
+    Exhausted,
+}
+
+/// The five fields of a cron expression, in the order they are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    Minute,
+    Hour,
+    DayOfMonth,
+    Month,
+    DayOfWeek,
+}
+
+impl Field {
+    /// All fields in expression order
+    pub const ALL: [Self; 5] = [
+        Self::Minute,
+        Self::Hour,
+        Self::DayOfMonth,
+        Self::Month,
+        Self::DayOfWeek,
+    ];
+
+    /// Smallest value allowed in the field
+    #[must_use]
+    pub const fn min(self) -> u32 {
+        match self {
+            Self::DayOfMonth | Self::Month => 1,
+            Self::Minute | Self::Hour | Self::DayOfWeek => 0,
+        }
+    }
+
+    /// Largest value allowed in the field
+    #[must_use]
+    pub const fn max(self) -> u32 {
+        match self {
+            Self::Minute => 59,
+            Self::Hour => 23,
+            Self::DayOfMonth => 31,
+            Self::Month => 12,
+            Self::DayOfWeek => 6,
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Minute => write!(f, "minute"),
+            Self::Hour => write!(f, "hour"),
+            Self::DayOfMonth => write!(f, "day of month"),
+            Self::Month => write!(f, "month"),
+            Self::DayOfWeek => write!(f, "day of week"),
+        }
+    }
+}
+
+/// An error found in a single field of a cron expression
+#[derive(Debug)]
+pub struct FieldError {
+    pub field: Field,
+    pub error: ParseError,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.error)
+    }
+}
+
+impl Error for FieldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,20 +188,36 @@ enum Dow {
     Sat = 6,
 }
 
+impl Dow {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Sun => "SUN",
+            Self::Mon => "MON",
+            Self::Tue => "TUE",
+            Self::Wed => "WED",
+            Self::Thu => "THU",
+            Self::Fri => "FRI",
+            Self::Sat => "SAT",
+        }
+    }
+}
+
 impl FromStr for Dow {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match &*s.to_uppercase() {
-            "SUN" => Ok(Self::Sun),
-            "MON" => Ok(Self::Mon),
-            "TUE" => Ok(Self::Tue),
-            "WED" => Ok(Self::Wed),
-            "THU" => Ok(Self::Thu),
-            "FRI" => Ok(Self::Fri),
-            "SAT" => Ok(Self::Sat),
-            _ => Err(()),
-        }
+        [
+            Self::Sun,
+            Self::Mon,
+            Self::Tue,
+            Self::Wed,
+            Self::Thu,
+            Self::Fri,
+            Self::Sat,
+        ]
+        .into_iter()
+        .find(|dow| dow.name().eq_ignore_ascii_case(s))
+        .ok_or(())
     }
 }
 
@@ -77,6 +230,18 @@ impl fmt::Display for ParseError {
             Self::ParseIntError(ref err) => err.fmt(f),
             Self::TryFromIntError(ref err) => err.fmt(f),
             Self::InvalidTimezone => write!(f, "invalid timezone"),
+            #[cfg(feature = "alloc")]
+            Self::InvalidFields(ref errors) => {
+                write!(f, "invalid fields")?;
+                for (i, err) in errors.iter().enumerate() {
+                    write!(f, "{}{err}", if i == 0 { ": " } else { ", " })?;
+                }
+                Ok(())
+            }
+            Self::NeverMatches => write!(f, "cron never matches a valid date"),
+            Self::HorizonExceeded => write!(f, "no match found within the search horizon"),
+            Self::UnsupportedPhrase(offset) => write!(f, "unsupported phrase at byte {offset}"),
+            Self::Exhausted => write!(f, "no dates left within the bounds"),
         }
     }
 }
@@ -109,6 +274,9 @@ impl From<num::TryFromIntError> for ParseError {
 /// * * * * * <command to execute>
 /// ```
 ///
+/// Returns the first whole minute strictly after `dt`, the seconds and
+/// nanoseconds of `dt` are only used to compare, the result never has any.
+///
 /// Example
 /// ```
 /// use cron_parser::parse;
@@ -123,104 +291,38 @@ impl From<num::TryFromIntError> for ParseError {
 /// # Errors
 /// [`ParseError`](enum.ParseError.html)
 pub fn parse<TZ: TimeZone>(cron: &str, dt: &DateTime<TZ>) -> Result<DateTime<TZ>, ParseError> {
-    let tz = dt.timezone();
-
-    let fields: Vec<&str> = cron.split_whitespace().collect();
-    let [
-        minute_str,
-        hour_str,
-        day_of_month_str,
-        month_str,
-        day_of_week_str,
-    ] = fields.as_slice()
-    else {
-        return Err(ParseError::InvalidCron);
-    };
-
-    let mut next = match Utc.from_local_datetime(&dt.naive_local()) {
-        chrono::LocalResult::Single(datetime) => datetime + Duration::minutes(1),
-        chrono::LocalResult::Ambiguous(earlier, _later) => earlier + Duration::minutes(1),
-        chrono::LocalResult::None => return Err(ParseError::InvalidTimezone),
-    };
-
-    next = make_utc_datetime(
-        next.year(),
-        next.month(),
-        next.day(),
-        next.hour(),
-        next.minute(),
-        0,
-    )?;
-
-    let result = loop {
-        // only try until next leap year
-        if next.year() - dt.year() > 4 {
-            return Err(ParseError::InvalidCron);
-        }
-
-        // * * * <month> *
-        let month = parse_field(month_str, 1, 12)?;
-        if !month.contains(&next.month()) {
-            next = make_utc_datetime(
-                if next.month() == 12 {
-                    next.year() + 1
-                } else {
-                    next.year()
-                },
-                if next.month() == 12 {
-                    1
-                } else {
-                    next.month() + 1
-                },
-                1,
-                0,
-                0,
-                0,
-            )?;
-            continue;
-        }
-
-        // * * <dom> * *
-        let do_m = parse_field(day_of_month_str, 1, 31)?;
-        if !do_m.contains(&next.day()) {
-            next += Duration::days(1);
-            next = make_utc_datetime(next.year(), next.month(), next.day(), 0, 0, 0)?;
-            continue;
-        }
-
-        // * <hour> * * *
-        let hour = parse_field(hour_str, 0, 23)?;
-        if !hour.contains(&next.hour()) {
-            next += Duration::hours(1);
-            next = make_utc_datetime(next.year(), next.month(), next.day(), next.hour(), 0, 0)?;
-            continue;
-        }
-
-        // <minute> * * * *
-        let minute = parse_field(minute_str, 0, 59)?;
-        if !minute.contains(&next.minute()) {
-            next += Duration::minutes(1);
-            continue;
-        }
-
-        // * * * * <dow>
-        let do_w = parse_field(day_of_week_str, 0, 6)?;
-        if !do_w.contains(&next.weekday().num_days_from_sunday()) {
-            next += Duration::days(1);
-            continue;
-        }
-
-        // Valid datetime for the timezone
-        match tz.from_local_datetime(&next.naive_local()) {
-            chrono::LocalResult::Single(dt) => break dt,
-            chrono::LocalResult::Ambiguous(earlier, _later) => break earlier,
-            chrono::LocalResult::None => {
-                next += Duration::minutes(1);
-            }
-        }
-    };
+    // every field is validated before searching, so errors don't depend on `dt`
+    Schedule::from_str(cron)?.next_after(dt)
+}
 
-    Ok(result)
+/// Validate every field of a cron expression without searching for a date
+///
+/// All the fields are checked and every failure is reported at once. This is
+/// pure: the result only depends on the expression, never on the current time.
+///
+/// Example
+/// ```
+/// use cron_parser::{validate, Field, ParseError};
+///
+/// assert!(validate("*/5 * * * *").is_ok());
+///
+/// match validate("60 24 * * Foo") {
+///     Err(ParseError::InvalidFields(errors)) => {
+///         let fields: Vec<Field> = errors.iter().map(|e| e.field).collect();
+///         assert_eq!(fields, vec![Field::Minute, Field::Hour, Field::DayOfWeek]);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+/// # Errors
+/// [`ParseError::InvalidCron`](enum.ParseError.html) if the expression does
+/// not have five fields, [`ParseError::InvalidFields`](enum.ParseError.html)
+/// with one entry per invalid field (without the `alloc` feature, the error
+/// of the first invalid field), or
+/// [`ParseError::NeverMatches`](enum.ParseError.html) if the fields are valid
+/// but no month contains any of the days, e.g. `0 0 30 2 *`.
+pub fn validate(cron: &str) -> Result<(), ParseError> {
+    Schedule::from_str(cron).map(|_| ())
 }
 
 /// `parse_field`
@@ -279,19 +381,29 @@ pub fn parse<TZ: TimeZone>(cron: &str, dt: &DateTime<TZ>) -> Result<DateTime<TZ>
 ///
 /// # Errors
 /// [`ParseError`](enum.ParseError.html)
+#[cfg(feature = "alloc")]
 pub fn parse_field(field: &str, min: u32, max: u32) -> Result<BTreeSet<u32>, ParseError> {
     let mut values = BTreeSet::<u32>::new();
+    parse_field_values(field, min, max, |value| {
+        values.insert(value);
+    })?;
+    Ok(values)
+}
 
-    // split fields by ','
-    let fields: Vec<&str> = field.split(',').filter(|s| !s.is_empty()).collect();
-
-    // iterate over the fields and match against allowed characters
-    for field in fields {
+// helper function parsing a cron field, calling `insert` for every value
+fn parse_field_values<F: FnMut(u32)>(
+    field: &str,
+    min: u32,
+    max: u32,
+    mut insert: F,
+) -> Result<(), ParseError> {
+    // split fields by ',' and iterate over them matching against allowed characters
+    for field in field.split(',').filter(|s| !s.is_empty()) {
         match field {
             // any
             "*" => {
                 for i in min..=max {
-                    values.insert(i);
+                    insert(i);
                 }
             }
 
@@ -304,14 +416,13 @@ pub fn parse_field(field: &str, min: u32, max: u32) -> Result<BTreeSet<u32>, Par
                 }
 
                 for i in (min..=max).step_by(step as usize) {
-                    values.insert(i);
+                    insert(i);
                 }
             }
 
             // step with range, eg: 12-18/2
             f if f.contains('/') => {
-                let tmp_fields: Vec<&str> = f.split('/').collect();
-                let [range_part, step_part] = tmp_fields.as_slice() else {
+                let Some((range_part, step_part)) = split_pair(f, '/') else {
                     return Err(ParseError::InvalidRange);
                 };
 
@@ -324,8 +435,7 @@ pub fn parse_field(field: &str, min: u32, max: u32) -> Result<BTreeSet<u32>, Par
 
                 // check for range, eg: 12-18
                 if range_part.contains('-') {
-                    let tmp_range: Vec<&str> = range_part.split('-').collect();
-                    let [start_str, end_str] = tmp_range.as_slice() else {
+                    let Some((start_str, end_str)) = split_pair(range_part, '-') else {
                         return Err(ParseError::InvalidRange);
                     };
 
@@ -337,21 +447,20 @@ pub fn parse_field(field: &str, min: u32, max: u32) -> Result<BTreeSet<u32>, Par
                     }
 
                     for i in (start..=end).step_by(step as usize) {
-                        values.insert(i);
+                        insert(i);
                     }
                 } else {
                     let start = parse_cron_value(range_part, min, max)?;
 
                     for i in (start..=max).step_by(step as usize) {
-                        values.insert(i);
+                        insert(i);
                     }
                 }
             }
 
             // range of values, it can have days of week like Wed-Fri
             f if f.contains('-') => {
-                let tmp_fields: Vec<&str> = f.split('-').collect();
-                let [start_str, end_str] = tmp_fields.as_slice() else {
+                let Some((start_str, end_str)) = split_pair(f, '-') else {
                     return Err(ParseError::InvalidRange);
                 };
 
@@ -362,19 +471,26 @@ pub fn parse_field(field: &str, min: u32, max: u32) -> Result<BTreeSet<u32>, Par
                     return Err(ParseError::InvalidRange);
                 }
                 for i in start..=end {
-                    values.insert(i);
+                    insert(i);
                 }
             }
 
             // integers or days of week any other will return an error
             _ => {
                 let value = parse_cron_value(field, min, max)?;
-                values.insert(value);
+                insert(value);
             }
         }
     }
 
-    Ok(values)
+    Ok(())
+}
+
+// helper function to split `a<sep>b`, None if there isn't exactly one separator
+fn split_pair(value: &str, sep: char) -> Option<(&str, &str)> {
+    value
+        .split_once(sep)
+        .filter(|(_, right)| !right.contains(sep))
 }
 
 // helper function to parse cron values
@@ -410,6 +526,7 @@ fn make_utc_datetime(
 #[allow(clippy::expect_used)]
 mod tests {
     use super::*;
+    use chrono::{Datelike, Timelike};
 
     #[test]
     fn test_make_utc_datetime_valid() {
@@ -501,6 +618,41 @@ mod tests {
         // Test InvalidTimezone
         let err = ParseError::InvalidTimezone;
         assert_eq!(format!("{err}"), "invalid timezone");
+
+        // Test NeverMatches
+        let err = ParseError::NeverMatches;
+        assert_eq!(format!("{err}"), "cron never matches a valid date");
+
+        // Test HorizonExceeded
+        let err = ParseError::HorizonExceeded;
+        assert_eq!(format!("{err}"), "no match found within the search horizon");
+
+        // Test UnsupportedPhrase
+        let err = ParseError::UnsupportedPhrase(6);
+        assert_eq!(format!("{err}"), "unsupported phrase at byte 6");
+
+        // Test Exhausted
+        let err = ParseError::Exhausted;
+        assert_eq!(format!("{err}"), "no dates left within the bounds");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_invalid_fields_display() {
+        let err = ParseError::InvalidFields(vec![
+            FieldError {
+                field: Field::Hour,
+                error: ParseError::InvalidValue,
+            },
+            FieldError {
+                field: Field::Month,
+                error: ParseError::InvalidRange,
+            },
+        ]);
+        assert_eq!(
+            format!("{err}"),
+            "invalid fields: hour: invalid value, month: invalid input"
+        );
     }
 
     #[test]
@@ -0,0 +1,344 @@
+use core::fmt;
+
+/// Words and phrases used by [`Schedule::describe_in`](struct.Schedule.html#method.describe_in)
+///
+/// Phrases are templates where `{0}`, `{1}` and `{2}` are replaced by their
+/// arguments, listed for each phrase. Start from one of the packs to adapt
+/// it:
+///
+/// ```
+/// use cron_parser::{Locale, Schedule};
+///
+/// let mut locale = Locale::ENGLISH;
+/// locale.at_times = "Daily at {0}";
+///
+/// let schedule: Schedule = "30 9 * * *".parse().unwrap();
+/// assert_eq!(schedule.describe_in(&locale).to_string(), "Daily at 09:30");
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct Locale {
+    /// Names of the days of the week, from Sunday
+    pub weekdays: [&'static str; 7],
+    /// Names of the months, from January
+    pub months: [&'static str; 12],
+    /// Write a number as an ordinal, e.g. `2nd`, or just the number when the
+    /// `every_nth` phrases have the suffix, e.g. `cada {0}.ª hora`
+    pub ordinal: fn(u32, &mut fmt::Formatter) -> fmt::Result,
+    /// Minutes
+    pub minute: Unit,
+    /// Hours
+    pub hour: Unit,
+    /// Days of the month
+    pub day: Unit,
+    /// Days of the week
+    pub weekday: Unit,
+    /// Months
+    pub month: Unit,
+    /// `Every minute`
+    pub every_minute: &'static str,
+    /// `Every {0} minutes`, the step
+    pub every_minutes: &'static str,
+    /// `At {0}`, a list of times such as `09:30`
+    pub at_times: &'static str,
+    /// `At {0}`, the minutes
+    pub at_minutes: &'static str,
+    /// ` past the hour`, after the minutes when every hour matches
+    pub past_the_hour: &'static str,
+    /// ` between {0} and {1}`, the first and the last minute of the hours
+    pub between: &'static str,
+    /// ` during {0}`, the hours after `every_minute` or `every_minutes`
+    pub during: &'static str,
+    /// ` past {0}`, the hours after `at_minutes`
+    pub past: &'static str,
+    /// `, on {0} of the month`, the days of the month
+    pub days_of_month: &'static str,
+    /// `, {0}`, a single range of days of the week
+    pub weekday_range: &'static str,
+    /// `, only on {0}`, the days of the week
+    pub only_on: &'static str,
+    /// `, if it falls on {0}`, the days of the week after the days of the month
+    pub if_on: &'static str,
+    /// `, only in {0}`, the months
+    pub only_in: &'static str,
+    /// `{0} through {1}`, the first and the last value of a range
+    pub range: &'static str,
+    /// `{0} from {1} through {2}`, the `every_nth` phrase, the first and the
+    /// last value of a step
+    pub step: &'static str,
+    /// ` and `, before the last item of a list
+    pub and: &'static str,
+}
+
+/// How the values of a field are called in a [`Locale`](struct.Locale.html)
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct Unit {
+    /// `minute`, before a single number
+    pub singular: &'static str,
+    /// `minutes`, before several numbers
+    pub plural: &'static str,
+    /// `every {0} minute`, the ordinal of the step
+    pub every_nth: &'static str,
+}
+
+impl Unit {
+    const fn new(singular: &'static str, plural: &'static str, every_nth: &'static str) -> Self {
+        Self {
+            singular,
+            plural,
+            every_nth,
+        }
+    }
+}
+
+impl Locale {
+    /// English, used by [`Schedule::describe`](struct.Schedule.html#method.describe)
+    pub const ENGLISH: Self = Self {
+        weekdays: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        ordinal: english_ordinal,
+        minute: Unit::new("minute", "minutes", "every {0} minute"),
+        hour: Unit::new("hour", "hours", "every {0} hour"),
+        day: Unit::new("day", "days", "every {0} day"),
+        weekday: Unit::new(
+            "day of the week",
+            "days of the week",
+            "every {0} day of the week",
+        ),
+        month: Unit::new("month", "months", "every {0} month"),
+        every_minute: "Every minute",
+        every_minutes: "Every {0} minutes",
+        at_times: "At {0}",
+        at_minutes: "At {0}",
+        past_the_hour: " past the hour",
+        between: " between {0} and {1}",
+        during: " during {0}",
+        past: " past {0}",
+        days_of_month: ", on {0} of the month",
+        weekday_range: ", {0}",
+        only_on: ", only on {0}",
+        if_on: ", if it falls on {0}",
+        only_in: ", only in {0}",
+        range: "{0} through {1}",
+        step: "{0} from {1} through {2}",
+        and: " and ",
+    };
+
+    /// Spanish
+    pub const SPANISH: Self = Self {
+        weekdays: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        ordinal: plain_ordinal,
+        minute: Unit::new("minuto", "minutos", "cada {0}.º minuto"),
+        hour: Unit::new("hora", "horas", "cada {0}.ª hora"),
+        day: Unit::new("día", "días", "cada {0}.º día"),
+        weekday: Unit::new(
+            "día de la semana",
+            "días de la semana",
+            "cada {0}.º día de la semana",
+        ),
+        month: Unit::new("mes", "meses", "cada {0}.º mes"),
+        every_minute: "Cada minuto",
+        every_minutes: "Cada {0} minutos",
+        at_times: "A las {0}",
+        at_minutes: "En {0}",
+        past_the_hour: " de cada hora",
+        between: " entre las {0} y las {1}",
+        during: " durante {0}",
+        past: " de {0}",
+        days_of_month: ", {0} del mes",
+        weekday_range: ", de {0}",
+        only_on: ", solo en {0}",
+        if_on: ", si cae en {0}",
+        only_in: ", solo en {0}",
+        range: "{0} a {1}",
+        step: "{0} de {1} a {2}",
+        and: " y ",
+    };
+
+    /// German
+    pub const GERMAN: Self = Self {
+        weekdays: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        ordinal: plain_ordinal,
+        minute: Unit::new("Minute", "Minuten", "jeder {0}. Minute"),
+        hour: Unit::new("Stunde", "Stunden", "jeder {0}. Stunde"),
+        day: Unit::new("Tag", "Tagen", "jedem {0}. Tag"),
+        weekday: Unit::new("Wochentag", "Wochentagen", "jedem {0}. Wochentag"),
+        month: Unit::new("Monat", "Monaten", "Abstand von {0} Monaten"),
+        every_minute: "Jede Minute",
+        every_minutes: "Alle {0} Minuten",
+        at_times: "Um {0}",
+        at_minutes: "In {0}",
+        past_the_hour: " in jeder Stunde",
+        between: " zwischen {0} und {1}",
+        during: " während {0}",
+        past: " in {0}",
+        days_of_month: ", an {0} des Monats",
+        weekday_range: ", {0}",
+        only_on: ", nur an {0}",
+        if_on: ", nur wenn es {0} ist",
+        only_in: ", nur im {0}",
+        range: "{0} bis {1}",
+        step: "{0} von {1} bis {2}",
+        and: " und ",
+    };
+
+    /// Portuguese
+    pub const PORTUGUESE: Self = Self {
+        weekdays: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        ordinal: plain_ordinal,
+        minute: Unit::new("minuto", "minutos", "cada {0}º minuto"),
+        hour: Unit::new("hora", "horas", "cada {0}ª hora"),
+        day: Unit::new("dia", "dias", "cada {0}º dia"),
+        weekday: Unit::new("dia da semana", "dias da semana", "cada {0}º dia da semana"),
+        month: Unit::new("mês", "meses", "cada {0}º mês"),
+        every_minute: "A cada minuto",
+        every_minutes: "A cada {0} minutos",
+        at_times: "Às {0}",
+        at_minutes: "Em {0}",
+        past_the_hour: " de cada hora",
+        between: " entre {0} e {1}",
+        during: " durante {0}",
+        past: " de {0}",
+        days_of_month: ", {0} do mês",
+        weekday_range: ", de {0}",
+        only_on: ", somente {0}",
+        if_on: ", se cair em {0}",
+        only_in: ", somente em {0}",
+        range: "{0} a {1}",
+        step: "{0} de {1} a {2}",
+        and: " e ",
+    };
+}
+
+// `2nd`, `11th`, `23rd`
+fn english_ordinal(number: u32, f: &mut fmt::Formatter) -> fmt::Result {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    write!(f, "{number}{suffix}")
+}
+
+// `2`, for phrases with the suffix of the ordinal
+fn plain_ordinal(number: u32, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{number}")
+}
+
+// helper function writing a phrase, replacing `{0}` to `{9}` with `args`,
+// other braces are written as they are
+pub(crate) fn write_template(
+    f: &mut fmt::Formatter,
+    template: &str,
+    args: &[&dyn fmt::Display],
+) -> fmt::Result {
+    let mut rest = template;
+    while let Some((before, after)) = rest.split_once('{') {
+        f.write_str(before)?;
+        let mut chars = after.chars();
+        let arg = match (chars.next(), chars.next()) {
+            (Some(digit), Some('}')) => digit
+                .to_digit(10)
+                .and_then(|index| args.get(usize::try_from(index).ok()?)),
+            _ => None,
+        };
+        if let Some(arg) = arg {
+            write!(f, "{arg}")?;
+            rest = chars.as_str();
+        } else {
+            f.write_str("{")?;
+            rest = after;
+        }
+    }
+    f.write_str(rest)
+}
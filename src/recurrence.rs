@@ -0,0 +1,270 @@
+use crate::{Bounded, Difference, Intersection, ParseError, Schedule, Union, combine::Bounds};
+#[cfg(feature = "alloc")]
+use crate::{BusinessDay, BusinessDays, Calendar, Excluding, OnBusinessDay};
+use chrono::{DateTime, TimeZone};
+use core::ops::{Bound, RangeBounds};
+
+/// Dates something fires at, shared by cron [`Schedule`](struct.Schedule.html)s
+/// and fixed [`Interval`](struct.Interval.html)s so a scheduler can use them
+/// the same way
+///
+/// Only [`first_in`](#tymethod.first_in), [`last_in`](#tymethod.last_in) and
+/// [`matches`](#tymethod.matches) are required, the other methods are built
+/// on them. Recurrences combine with [`union`](#method.union),
+/// [`intersection`](#method.intersection) and
+/// [`difference`](#method.difference).
+///
+/// Example
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cron_parser::{Interval, Recurrence, Schedule};
+///
+/// fn next_run<R: Recurrence>(recurrence: &R) -> i64 {
+///     let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 10, 0).unwrap();
+///     recurrence.next_after(&now).unwrap().timestamp()
+/// }
+///
+/// let schedule: Schedule = "0 * * * *".parse().unwrap();
+/// let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let interval = Interval::new(&anchor, Duration::minutes(90)).unwrap();
+///
+/// assert_eq!(next_run(&schedule), anchor.timestamp() + 3600);
+/// assert_eq!(next_run(&interval), anchor.timestamp() + 90 * 60);
+/// ```
+pub trait Recurrence {
+    /// First date within `range`, in the timezone of its start, `None` if
+    /// there is none
+    ///
+    /// # Errors
+    /// [`ParseError::InvalidRange`](enum.ParseError.html) if the range has no
+    /// start, [`ParseError`](enum.ParseError.html) if the search fails.
+    fn first_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError>;
+
+    /// Last date within `range`, in the timezone of its end, `None` if there
+    /// is none
+    ///
+    /// # Errors
+    /// [`ParseError::InvalidRange`](enum.ParseError.html) if the range has no
+    /// end, [`ParseError`](enum.ParseError.html) if the search fails.
+    fn last_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError>;
+
+    /// Check if `dt` is one of the dates
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool;
+
+    /// Years a search without an end covers before giving up, `None` if it
+    /// isn't limited
+    fn horizon(&self) -> Option<u32> {
+        None
+    }
+
+    /// Find the next date strictly after `dt`, in the timezone of `dt`
+    ///
+    /// # Errors
+    /// [`ParseError::HorizonExceeded`](enum.ParseError.html) if there is none
+    fn next_after<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> Result<DateTime<TZ>, ParseError> {
+        self.first_in((Bound::Excluded(dt.clone()), Bound::Unbounded))?
+            .ok_or(ParseError::HorizonExceeded)
+    }
+
+    /// Find the next date at or after `dt`, in the timezone of `dt`
+    ///
+    /// # Errors
+    /// [`ParseError::HorizonExceeded`](enum.ParseError.html) if there is none
+    fn next_at_or_after<TZ: TimeZone>(
+        &self,
+        dt: &DateTime<TZ>,
+    ) -> Result<DateTime<TZ>, ParseError> {
+        self.first_in(dt.clone()..)?
+            .ok_or(ParseError::HorizonExceeded)
+    }
+
+    /// Find the previous date strictly before `dt`, in the timezone of `dt`
+    ///
+    /// # Errors
+    /// [`ParseError::HorizonExceeded`](enum.ParseError.html) if there is none
+    fn prev_before<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> Result<DateTime<TZ>, ParseError> {
+        self.last_in(..dt.clone())?
+            .ok_or(ParseError::HorizonExceeded)
+    }
+
+    /// Find the previous date at or before `dt`, in the timezone of `dt`
+    ///
+    /// # Errors
+    /// [`ParseError::HorizonExceeded`](enum.ParseError.html) if there is none
+    fn prev_at_or_before<TZ: TimeZone>(
+        &self,
+        dt: &DateTime<TZ>,
+    ) -> Result<DateTime<TZ>, ParseError> {
+        self.last_in(..=dt.clone())?
+            .ok_or(ParseError::HorizonExceeded)
+    }
+
+    /// Iterate over the dates within `range`, in the timezone of its start
+    ///
+    /// # Errors
+    /// [`ParseError::InvalidRange`](enum.ParseError.html) if the range has no
+    /// start.
+    fn occurrences_between<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Occurrences<'_, TZ, Self>, ParseError>
+    where
+        Self: Sized,
+    {
+        Occurrences::new(self, range)
+    }
+
+    /// Ignore the dates before `dt`, see [`Bounded`](struct.Bounded.html)
+    fn not_before<TZ: TimeZone>(self, dt: &DateTime<TZ>) -> Bounded<Self>
+    where
+        Self: Sized,
+    {
+        Bounded::new(self).not_before(dt)
+    }
+
+    /// Ignore the dates after `dt`, see [`Bounded`](struct.Bounded.html)
+    fn not_after<TZ: TimeZone>(self, dt: &DateTime<TZ>) -> Bounded<Self>
+    where
+        Self: Sized,
+    {
+        Bounded::new(self).not_after(dt)
+    }
+
+    /// Keep the first `runs` dates from `dt`, see
+    /// [`Bounded`](struct.Bounded.html)
+    fn max_runs<TZ: TimeZone>(self, dt: &DateTime<TZ>, runs: u32) -> Bounded<Self>
+    where
+        Self: Sized,
+    {
+        Bounded::new(self).max_runs(dt, runs)
+    }
+
+    /// Dates of either `self` or `other`, see [`Union`](struct.Union.html)
+    fn union<B: Recurrence>(self, other: B) -> Union<Self, B>
+    where
+        Self: Sized,
+    {
+        Union::new(self, other)
+    }
+
+    /// Dates of `self` that `other` matches and the other way around, see
+    /// [`Intersection`](struct.Intersection.html)
+    fn intersection<B: Recurrence>(self, other: B) -> Intersection<Self, B>
+    where
+        Self: Sized,
+    {
+        Intersection::new(self, other)
+    }
+
+    /// Dates of `self` that `other` doesn't match, see
+    /// [`Difference`](struct.Difference.html)
+    fn difference<B: Recurrence>(self, other: B) -> Difference<Self, B>
+    where
+        Self: Sized,
+    {
+        Difference::new(self, other)
+    }
+
+    /// Dates the `calendar` doesn't exclude, see
+    /// [`Excluding`](struct.Excluding.html)
+    #[cfg(feature = "alloc")]
+    fn excluding(self, calendar: Calendar) -> Excluding<Self>
+    where
+        Self: Sized,
+    {
+        Excluding::new(self, calendar)
+    }
+
+    /// Dates on one business day of each month, see
+    /// [`OnBusinessDay`](struct.OnBusinessDay.html)
+    #[cfg(feature = "alloc")]
+    fn on_business_day(self, day: BusinessDay, days: BusinessDays) -> OnBusinessDay<Self>
+    where
+        Self: Sized,
+    {
+        OnBusinessDay::new(self, day, days)
+    }
+}
+
+/// Iterator over the dates of a [`Recurrence`](trait.Recurrence.html) within a
+/// range, created with [`Schedule::occurrences_between`](struct.Schedule.html#method.occurrences_between)
+/// or [`Recurrence::occurrences_between`](trait.Recurrence.html#method.occurrences_between)
+///
+/// Iteration stops early only if the search fails, e.g. beyond the last year
+/// chrono can represent.
+#[derive(Debug, Clone)]
+pub struct Occurrences<'a, TZ: TimeZone, R = Schedule> {
+    recurrence: &'a R,
+    // the previous date, or the start of the range
+    pub(crate) from: Option<DateTime<TZ>>,
+    inclusive: bool,
+    pub(crate) end: Bound<DateTime<TZ>>,
+    // the search for the next date, `first_in` unless the recurrence already
+    // narrowed the range
+    search: Search<R, TZ>,
+}
+
+type Search<R, TZ> = fn(&R, Bounds<TZ>) -> Result<Option<DateTime<TZ>>, ParseError>;
+
+impl<'a, TZ: TimeZone, R: Recurrence> Occurrences<'a, TZ, R> {
+    pub(crate) fn new<B: RangeBounds<DateTime<TZ>>>(
+        recurrence: &'a R,
+        range: B,
+    ) -> Result<Self, ParseError> {
+        let (from, inclusive) = match range.start_bound() {
+            Bound::Included(start) => (start.clone(), true),
+            Bound::Excluded(start) => (start.clone(), false),
+            Bound::Unbounded => return Err(ParseError::InvalidRange),
+        };
+
+        Ok(Self {
+            recurrence,
+            from: Some(from),
+            inclusive,
+            end: range.end_bound().cloned(),
+            search: |recurrence, range| recurrence.first_in(range),
+        })
+    }
+
+    // search for the next date with `search` instead of `first_in`
+    pub(crate) fn with_search(mut self, search: Search<R, TZ>) -> Self {
+        self.search = search;
+        self
+    }
+
+    // check if `dt` is before the end of the range
+    pub(crate) fn before_end(&self, dt: &DateTime<TZ>) -> bool {
+        match &self.end {
+            Bound::Included(end) => dt <= end,
+            Bound::Excluded(end) => dt < end,
+            Bound::Unbounded => true,
+        }
+    }
+}
+
+impl<TZ: TimeZone, R: Recurrence> Iterator for Occurrences<'_, TZ, R> {
+    type Item = DateTime<TZ>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let from = self.from.take()?;
+        let start = if self.inclusive {
+            Bound::Included(from)
+        } else {
+            Bound::Excluded(from)
+        };
+
+        let next = (self.search)(self.recurrence, (start, self.end.clone()))
+            .ok()
+            .flatten()?;
+
+        self.from = Some(next.clone());
+        self.inclusive = false;
+        Some(next)
+    }
+}
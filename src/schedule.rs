@@ -0,0 +1,1405 @@
+use crate::{
+    Description, Field, FieldError, FieldSet, Locale, Occurrences, ParseError, Recurrence,
+    field_set::{FieldDisplay, Star, Term, Terms},
+    make_utc_datetime,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Timelike, Utc,
+};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Bound, RangeBounds},
+    str::FromStr,
+};
+
+/// A compiled cron expression
+///
+/// Every field is parsed and validated once, up front, so searching for the
+/// next date never fails because of the syntax of the expression.
+///
+/// # Precision
+///
+/// A schedule fires at whole minutes of local time: every date returned has
+/// zero seconds and nanoseconds, whatever the precision of the start date.
+/// The seconds and nanoseconds of the start date are taken into account:
+/// * [`next_after`](#method.next_after) returns the first whole minute
+///   strictly after the start, `12:00:30` and `12:00:00` both give `12:01`
+///   for `* * * * *`.
+/// * [`next_at_or_after`](#method.next_at_or_after) also returns the start
+///   if it is a whole minute, `12:00:00` gives `12:00` but `12:00:00.001`
+///   gives `12:01`.
+/// * [`matches`](#method.matches) checks the minute containing the date,
+///   `12:00:30` matches `0 12 * * *`.
+///
+/// Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cron_parser::Schedule;
+///
+/// let schedule: Schedule = "0 12-18/3 * * *".parse().unwrap();
+/// let now = Utc.with_ymd_and_hms(2019, 11, 8, 13, 0, 0).unwrap();
+/// let next = schedule.next_after(&now).unwrap();
+/// assert_eq!(next, Utc.with_ymd_and_hms(2019, 11, 8, 15, 0, 0).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    pub(crate) minutes: FieldSet,
+    pub(crate) hours: FieldSet,
+    pub(crate) days_of_month: FieldSet,
+    pub(crate) months: FieldSet,
+    pub(crate) days_of_week: FieldSet,
+    horizon: Option<u32>,
+    gap_policy: GapPolicy,
+    overlap_policy: OverlapPolicy,
+    // the minute or the hour field starts with `*`, for `with_vixie_dst`
+    wildcard: bool,
+    vixie_dst: bool,
+}
+
+/// What to do with a date that doesn't exist in local time because the clocks
+/// go forward, e.g. `02:30` when a DST change moves `02:00` to `03:00`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum GapPolicy {
+    /// Don't run, the next date is the next one that exists (default)
+    #[default]
+    Skip,
+    /// Run at the first minute after the gap, `02:30` runs at `03:00`
+    FirstValid,
+    /// Run at the same time in the offset before the gap, `02:30` runs at
+    /// `03:30` for a one hour change
+    ///
+    /// A shifted date is returned even if dates exist between the end of the
+    /// gap and the shifted time, e.g. `03:10` for `50 2,3 * * *`, those are
+    /// skipped that day.
+    Shift,
+}
+
+/// Which of the two dates to use for a local time that happens twice because
+/// the clocks go back, e.g. `01:30` when a DST change moves `02:00` to `01:00`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OverlapPolicy {
+    /// Run in the first of the repeated hours only (default)
+    #[default]
+    Earlier,
+    /// Run in the second of the repeated hours only
+    Later,
+    /// Run in both of the repeated hours
+    Both,
+}
+
+impl FromStr for Schedule {
+    type Err = ParseError;
+
+    fn from_str(cron: &str) -> Result<Self, Self::Err> {
+        let mut fields = cron.split_whitespace();
+        let (Some(minute), Some(hour), Some(day_of_month), Some(month), Some(day_of_week), None) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            return Err(ParseError::InvalidCron);
+        };
+
+        // parse every field before reporting, so all the errors are returned,
+        // a field without values (e.g. `,,,`) is invalid
+        let mut errors = FieldErrors::default();
+        let mut compile = |field: Field, value: &str| {
+            let error = match FieldSet::parse(value, field.min(), field.max()) {
+                Ok(set) if !set.is_empty() => return set,
+                Ok(_) => ParseError::InvalidValue,
+                Err(error) => error,
+            };
+            errors.push(FieldError { field, error });
+            FieldSet::new()
+        };
+
+        let fields = [
+            compile(Field::Minute, minute),
+            compile(Field::Hour, hour),
+            compile(Field::DayOfMonth, day_of_month),
+            compile(Field::Month, month),
+            compile(Field::DayOfWeek, day_of_week),
+        ];
+
+        errors.into_result()?;
+
+        Self::from_fields(fields, minute.starts_with('*') || hour.starts_with('*'))
+    }
+}
+
+/// Canonical, minimal expression of the schedule
+///
+/// Each field is written with disjoint terms, sorted by their first value,
+/// using the fewest characters. A range or a step covers at least 3 values
+/// and a step is only written when it is shorter than listing its values, so
+/// `1,3,5` stays a list and `0,10,20,30` is written `0-30/10`. Expressions
+/// firing at the same times are written the same way and parse back to the
+/// same schedule. Days of the week and months are numbers, the search horizon
+/// and the DST policies are not part of the expression.
+///
+/// The minute or hour field starts with `*` if and only if it does in the
+/// parsed expression, since [`with_vixie_dst`](#method.with_vixie_dst)
+/// depends on it: `0,15,30,45 3 * * *` is written `0-45/15 3 * * *` and
+/// `0-59 0-23 * * *` stays as it is.
+///
+/// The search for the shortest terms is bounded: for an irregular field, such
+/// as 30 scattered minutes, it can stop before proving a shorter expression
+/// doesn't exist and write the shortest one found.
+///
+/// Example
+/// ```
+/// use cron_parser::Schedule;
+///
+/// let schedule: Schedule = "0,15,30,45 * * 1,2,3 Mon,Tue,Wed".parse().unwrap();
+/// assert_eq!(schedule.to_string(), "*/15 * * 1-3 1-3");
+/// assert_eq!(schedule.to_string(), "*/15 * * 1-3 1-3".parse::<Schedule>().unwrap().to_string());
+///
+/// let schedule: Schedule = "0,15,30,45 9,10,11,12 * * *".parse().unwrap();
+/// assert_eq!(schedule.to_string(), "0-45/15 9-12 * * *");
+/// ```
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // keep the minute or hour field starting with `*` for wildcard jobs
+        let starts_with_star = |set: FieldSet, field: Field| {
+            matches!(
+                Terms::new(set, field).next(),
+                Some(Term::All | Term::Every(_))
+            )
+        };
+        let (minutes, hours) = if !self.wildcard {
+            (Star::Never, Star::Never)
+        } else if starts_with_star(self.minutes, Field::Minute)
+            || starts_with_star(self.hours, Field::Hour)
+        {
+            (Star::Allowed, Star::Allowed)
+        } else if Terms::starting_with_every(self.minutes, Field::Minute).is_some() {
+            (Star::First, Star::Allowed)
+        } else {
+            (Star::Allowed, Star::First)
+        };
+
+        write!(
+            f,
+            "{} {} {} {} {}",
+            FieldDisplay(self.minutes, Field::Minute, minutes),
+            FieldDisplay(self.hours, Field::Hour, hours),
+            FieldDisplay(self.days_of_month, Field::DayOfMonth, Star::Allowed),
+            FieldDisplay(self.months, Field::Month, Star::Allowed),
+            FieldDisplay(self.days_of_week, Field::DayOfWeek, Star::Allowed),
+        )
+    }
+}
+
+/// Schedules are equal when their compiled fields and their DST settings are,
+/// however the expressions are written
+///
+/// `0 0 * * 0`, `0 0 * * Sun` and `0 0 1-31 * SUN` are equal. The search
+/// horizon is not compared, it limits the search but not the dates. Use
+/// [`is_equivalent`](struct.Schedule.html#method.is_equivalent) to also
+/// ignore days that never happen.
+///
+/// Example
+/// ```
+/// use cron_parser::Schedule;
+///
+/// let sunday: Schedule = "0 0 * * 0".parse().unwrap();
+/// assert_eq!(sunday, "0 0 1-31 * SUN".parse().unwrap());
+/// assert_ne!(sunday, "0 0 * * 0".parse::<Schedule>().unwrap().with_vixie_dst());
+/// ```
+impl PartialEq for Schedule {
+    fn eq(&self, other: &Self) -> bool {
+        self.minutes == other.minutes
+            && self.hours == other.hours
+            && self.days_of_month == other.days_of_month
+            && self.months == other.months
+            && self.days_of_week == other.days_of_week
+            && self.dst_settings() == other.dst_settings()
+    }
+}
+
+impl Eq for Schedule {}
+
+impl Hash for Schedule {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.minutes.hash(state);
+        self.hours.hash(state);
+        self.days_of_month.hash(state);
+        self.months.hash(state);
+        self.days_of_week.hash(state);
+        self.dst_settings().hash(state);
+    }
+}
+
+impl Schedule {
+    /// Number of years searched by default, enough to reach the next leap year
+    pub const DEFAULT_HORIZON: u32 = 4;
+
+    /// Longest change of offset handled as a DST change by
+    /// [`with_vixie_dst`](#method.with_vixie_dst), in seconds
+    pub const VIXIE_MAX_DST_SHIFT: i32 = 3 * 3600;
+
+    /// Limit the search to dates whose year is at most `years` after the year
+    /// of the start date, in its timezone
+    ///
+    /// Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_parser::{ParseError, Schedule};
+    ///
+    /// // Feb 29 on a Sunday
+    /// let schedule: Schedule = "0 0 29 2 0".parse().unwrap();
+    /// let now = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    /// assert!(matches!(schedule.next_after(&now), Err(ParseError::HorizonExceeded)));
+    ///
+    /// let next = schedule.with_horizon(12).next_after(&now).unwrap();
+    /// assert_eq!(next, Utc.with_ymd_and_hms(2032, 2, 29, 0, 0, 0).unwrap());
+    /// ```
+    #[must_use]
+    pub const fn with_horizon(mut self, years: u32) -> Self {
+        self.horizon = Some(years);
+        self
+    }
+
+    /// Search without a year limit
+    ///
+    /// This always terminates: an expression whose days never exist is
+    /// rejected with [`ParseError::NeverMatches`](enum.ParseError.html) when
+    /// compiled, and any other date falls on every weekday within 400 years.
+    #[must_use]
+    pub const fn without_horizon(mut self) -> Self {
+        self.horizon = None;
+        self
+    }
+
+    // schedule firing at the values of the fields, in expression order, with
+    // the default settings, `wildcard` if the minute or hour field of its
+    // expression starts with `*`
+    pub(crate) fn from_fields(fields: [FieldSet; 5], wildcard: bool) -> Result<Self, ParseError> {
+        let [minutes, hours, days_of_month, months, days_of_week] = fields;
+        let schedule = Self {
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            horizon: Some(Self::DEFAULT_HORIZON),
+            gap_policy: GapPolicy::Skip,
+            overlap_policy: OverlapPolicy::Earlier,
+            wildcard,
+            vixie_dst: false,
+        };
+
+        // days like Feb 30 or Apr 31 never exist, no need to search for them
+        if !schedule.can_match() {
+            return Err(ParseError::NeverMatches);
+        }
+
+        Ok(schedule)
+    }
+
+    // check that any of the months has at least one of the days of the month,
+    // Feb 29 counts since it exists in leap years, and that no field is empty
+    // although parsing and building already reject empty fields
+    fn can_match(&self) -> bool {
+        if self.minutes.is_empty() || self.hours.is_empty() || self.days_of_week.is_empty() {
+            return false;
+        }
+
+        self.months.iter().any(|month| {
+            self.days_of_month
+                .first()
+                .is_some_and(|day| day <= max_days_in_month(month))
+        })
+    }
+
+    // find the first day from `day` until the end of the month matching both
+    // the day of month and the day of week
+    fn next_day(&self, year: i32, month: u32, day: u32) -> Option<u32> {
+        let last = days_in_month(year, month);
+        let first_weekday = NaiveDate::from_ymd_opt(year, month, 1)?
+            .weekday()
+            .num_days_from_sunday();
+
+        let mut day = self.days_of_month.next_from(day)?;
+        while day <= last {
+            if self.days_of_week.contains((first_weekday + day - 1) % 7) {
+                return Some(day);
+            }
+            day = self.days_of_month.next_from(day + 1)?;
+        }
+        None
+    }
+
+    /// Set how dates that don't exist in local time, because the clocks go
+    /// forward, are handled, [`GapPolicy::Skip`](enum.GapPolicy.html) by default
+    ///
+    /// Example
+    /// ```
+    /// use chrono::TimeZone;
+    /// use chrono_tz::US::Pacific;
+    /// use cron_parser::{GapPolicy, Schedule};
+    ///
+    /// // 2024-03-10 02:00 doesn't exist in Pacific time, clocks go to 03:00
+    /// let schedule: Schedule = "30 2 * * *".parse().unwrap();
+    /// let before = Pacific.with_ymd_and_hms(2024, 3, 10, 1, 0, 0).unwrap();
+    ///
+    /// let next = schedule.clone().next_after(&before).unwrap();
+    /// assert_eq!(next, Pacific.with_ymd_and_hms(2024, 3, 11, 2, 30, 0).unwrap());
+    ///
+    /// let first_valid = schedule.clone().with_gap_policy(GapPolicy::FirstValid);
+    /// let next = first_valid.next_after(&before).unwrap();
+    /// assert_eq!(next, Pacific.with_ymd_and_hms(2024, 3, 10, 3, 0, 0).unwrap());
+    ///
+    /// let shift = schedule.with_gap_policy(GapPolicy::Shift);
+    /// let next = shift.next_after(&before).unwrap();
+    /// assert_eq!(next, Pacific.with_ymd_and_hms(2024, 3, 10, 3, 30, 0).unwrap());
+    /// ```
+    #[must_use]
+    pub const fn with_gap_policy(mut self, policy: GapPolicy) -> Self {
+        self.gap_policy = policy;
+        self
+    }
+
+    /// Set how local times that happen twice, because the clocks go back, are
+    /// handled, [`OverlapPolicy::Earlier`](enum.OverlapPolicy.html) by default
+    ///
+    /// Example
+    /// ```
+    /// use chrono::{TimeZone, Timelike};
+    /// use chrono_tz::US::Pacific;
+    /// use cron_parser::{OverlapPolicy, Schedule};
+    ///
+    /// // 2024-11-03 01:00 - 01:59 happens twice in Pacific time
+    /// let schedule = "30 1 * * *"
+    ///     .parse::<Schedule>()
+    ///     .unwrap()
+    ///     .with_overlap_policy(OverlapPolicy::Both);
+    /// let start = Pacific.with_ymd_and_hms(2024, 11, 3, 0, 0, 0).unwrap();
+    /// let end = Pacific.with_ymd_and_hms(2024, 11, 3, 3, 0, 0).unwrap();
+    /// let dates: Vec<_> = schedule.occurrences_between(start..end).unwrap().collect();
+    /// assert_eq!(dates.len(), 2);
+    /// assert!(dates.iter().all(|dt| dt.hour() == 1 && dt.minute() == 30));
+    /// ```
+    #[must_use]
+    pub const fn with_overlap_policy(mut self, policy: OverlapPolicy) -> Self {
+        self.overlap_policy = policy;
+        self
+    }
+
+    /// Handle DST changes like Vixie cron, instead of the
+    /// [`GapPolicy`](enum.GapPolicy.html) and the
+    /// [`OverlapPolicy`](enum.OverlapPolicy.html)
+    ///
+    /// Vixie cron tells wildcard jobs, whose minute or hour field starts with
+    /// `*`, from fixed-time jobs. Schedules from
+    /// [`from_english`](#method.from_english) and the
+    /// [`builder`](#method.builder) follow the expression they are written
+    /// as. For a change of offset shorter than
+    /// [`VIXIE_MAX_DST_SHIFT`](#associatedconstant.VIXIE_MAX_DST_SHIFT):
+    /// * when the clocks go forward, wildcard jobs skip the missing times
+    ///   and fixed-time jobs due in the gap run at the first minute after it.
+    /// * when the clocks go back, wildcard jobs run in both of the repeated
+    ///   hours and fixed-time jobs only in the first one.
+    ///
+    /// Longer changes are handled as the clock being set: missing times are
+    /// skipped and repeated times run again, whatever the job.
+    ///
+    /// Example
+    /// ```
+    /// use chrono::TimeZone;
+    /// use chrono_tz::US::Pacific;
+    /// use cron_parser::Schedule;
+    ///
+    /// // 2024-03-10 02:00 doesn't exist in Pacific time, clocks go to 03:00
+    /// let before = Pacific.with_ymd_and_hms(2024, 3, 10, 1, 0, 0).unwrap();
+    ///
+    /// let fixed = "30 2 * * *".parse::<Schedule>().unwrap().with_vixie_dst();
+    /// let next = fixed.next_after(&before).unwrap();
+    /// assert_eq!(next, Pacific.with_ymd_and_hms(2024, 3, 10, 3, 0, 0).unwrap());
+    ///
+    /// let wildcard = "*/30 2 * * *".parse::<Schedule>().unwrap().with_vixie_dst();
+    /// let next = wildcard.next_after(&before).unwrap();
+    /// assert_eq!(next, Pacific.with_ymd_and_hms(2024, 3, 11, 2, 0, 0).unwrap());
+    /// ```
+    #[must_use]
+    pub const fn with_vixie_dst(mut self) -> Self {
+        self.vixie_dst = true;
+        self
+    }
+
+    // settings changing the dates around DST changes, Vixie cron only depends
+    // on whether the schedule is a wildcard job
+    const fn dst_settings(&self) -> (GapPolicy, OverlapPolicy, Option<bool>) {
+        if self.vixie_dst {
+            (GapPolicy::Skip, OverlapPolicy::Earlier, Some(self.wildcard))
+        } else {
+            (self.gap_policy, self.overlap_policy, None)
+        }
+    }
+
+    // policy for a local time in a gap of `shift` seconds
+    const fn gap_policy_for(&self, shift: i32) -> GapPolicy {
+        if !self.vixie_dst {
+            self.gap_policy
+        } else if self.wildcard || shift >= Self::VIXIE_MAX_DST_SHIFT {
+            GapPolicy::Skip
+        } else {
+            GapPolicy::FirstValid
+        }
+    }
+
+    // policy for a local time repeated by a change of `shift` seconds
+    const fn overlap_policy_for(&self, shift: i32) -> OverlapPolicy {
+        if !self.vixie_dst {
+            self.overlap_policy
+        } else if self.wildcard || shift >= Self::VIXIE_MAX_DST_SHIFT {
+            OverlapPolicy::Both
+        } else {
+            OverlapPolicy::Earlier
+        }
+    }
+
+    /// Describe the schedule in English, e.g. `At 09:30, Monday through Friday`
+    ///
+    /// The description follows the compiled fields, so equivalent expressions
+    /// are described the same way, and it says that both the day of month and
+    /// the day of week must match when both are restricted.
+    ///
+    /// Example
+    /// ```
+    /// use cron_parser::Schedule;
+    ///
+    /// let schedule: Schedule = "30 9 * * Mon-Fri".parse().unwrap();
+    /// assert_eq!(schedule.describe().to_string(), "At 09:30, Monday through Friday");
+    ///
+    /// let schedule: Schedule = "*/15 12-18 * 3 *".parse().unwrap();
+    /// assert_eq!(
+    ///     schedule.describe().to_string(),
+    ///     "Every 15 minutes between 12:00 and 18:59, only in March"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn describe(&self) -> Description<'_> {
+        Description::new(self, &Locale::ENGLISH)
+    }
+
+    /// Describe the schedule in the language of `locale`, see
+    /// [`describe`](#method.describe)
+    ///
+    /// Example
+    /// ```
+    /// use cron_parser::{Locale, Schedule};
+    ///
+    /// let schedule: Schedule = "*/15 12-18 * 3 *".parse().unwrap();
+    /// assert_eq!(
+    ///     schedule.describe_in(&Locale::SPANISH).to_string(),
+    ///     "Cada 15 minutos entre las 12:00 y las 18:59, solo en marzo"
+    /// );
+    /// assert_eq!(
+    ///     schedule.describe_in(&Locale::GERMAN).to_string(),
+    ///     "Alle 15 Minuten zwischen 12:00 und 18:59, nur im März"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn describe_in<'a>(&'a self, locale: &'a Locale) -> Description<'a> {
+        Description::new(self, locale)
+    }
+
+    /// Check if both schedules fire at exactly the same dates
+    ///
+    /// Unlike `==`, days of the month that no selected month has and months
+    /// without any of the days of the month are ignored, e.g. `0 0 31 * *` is
+    /// equivalent to `0 0 31 1,3,5,7,8,10,12 *`. Both the day of month and
+    /// the day of week must match, and over the years every day of every
+    /// month falls on every day of the week, so the remaining fields must be
+    /// equal. The DST settings are compared, the search horizon is not.
+    ///
+    /// Example
+    /// ```
+    /// use cron_parser::Schedule;
+    ///
+    /// let last: Schedule = "0 0 30,31 2,4 *".parse().unwrap();
+    /// assert_ne!(last, "0 0 30 4 *".parse().unwrap());
+    /// assert!(last.is_equivalent(&"0 0 30 4 *".parse().unwrap()));
+    /// assert!(!last.is_equivalent(&"0 0 30 4 Mon".parse().unwrap()));
+    /// ```
+    #[must_use]
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        self.minutes == other.minutes
+            && self.hours == other.hours
+            && self.days() == other.days()
+            && self.days_of_week == other.days_of_week
+            && self.dst_settings() == other.dst_settings()
+    }
+
+    // the days of the month and the months that happen together, Feb 29
+    // happens in leap years
+    fn days(&self) -> (FieldSet, FieldSet) {
+        let months: FieldSet = self
+            .months
+            .iter()
+            .filter(|&month| {
+                self.days_of_month
+                    .first()
+                    .is_some_and(|day| day <= max_days_in_month(month))
+            })
+            .collect();
+        let longest = months.iter().map(max_days_in_month).max().unwrap_or(0);
+        let days = self
+            .days_of_month
+            .iter()
+            .filter(|&day| day <= longest)
+            .collect();
+        (days, months)
+    }
+
+    /// Check if the schedule fires at `dt`, in the timezone of `dt`
+    ///
+    /// Schedules have minute precision, seconds and nanoseconds are ignored so
+    /// a tick at `12:00:00.250` matches `0 12 * * *`. Like the search, both the
+    /// day of month and the day of week must match. Through the
+    /// [`Recurrence`](trait.Recurrence.html) trait, used by the combinators,
+    /// only the whole minutes the schedule fires at match, following the DST
+    /// policies.
+    ///
+    /// Only the fields are checked against the local time of `dt`, the
+    /// [`GapPolicy`](enum.GapPolicy.html) and the
+    /// [`OverlapPolicy`](enum.OverlapPolicy.html) are not applied, use
+    /// [`next_at_or_after`](#method.next_at_or_after) to follow them.
+    ///
+    /// Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_parser::Schedule;
+    ///
+    /// let schedule: Schedule = "*/15 9-17 * * Mon-Fri".parse().unwrap();
+    /// // Friday
+    /// assert!(schedule.matches(&Utc.with_ymd_and_hms(2019, 11, 8, 9, 45, 30).unwrap()));
+    /// assert!(!schedule.matches(&Utc.with_ymd_and_hms(2019, 11, 8, 9, 46, 0).unwrap()));
+    /// // Saturday
+    /// assert!(!schedule.matches(&Utc.with_ymd_and_hms(2019, 11, 9, 9, 45, 0).unwrap()));
+    /// ```
+    #[must_use]
+    pub fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        let local = dt.naive_local();
+        self.minutes.contains(local.minute())
+            && self.hours.contains(local.hour())
+            && self.days_of_month.contains(local.day())
+            && self.months.contains(local.month())
+            && self
+                .days_of_week
+                .contains(local.weekday().num_days_from_sunday())
+    }
+
+    /// Find the next date strictly after `dt`, in the timezone of `dt`
+    ///
+    /// The search gives up after [`DEFAULT_HORIZON`](#associatedconstant.DEFAULT_HORIZON)
+    /// years unless configured with [`with_horizon`](#method.with_horizon) or
+    /// [`without_horizon`](#method.without_horizon).
+    ///
+    /// # Errors
+    /// [`ParseError`](enum.ParseError.html)
+    pub fn next_after<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> Result<DateTime<TZ>, ParseError> {
+        self.next_from(dt, false, self.until_year(dt.year()))?
+            .ok_or(ParseError::HorizonExceeded)
+    }
+
+    /// Find the next date at or after `dt`, in the timezone of `dt`
+    ///
+    /// Unlike [`next_after`](#method.next_after), `dt` itself is returned if
+    /// the schedule fires at it, which is useful when restarting a scheduler
+    /// during the minute a job is due. Dates are whole minutes, so `dt` only
+    /// counts if it has no seconds or nanoseconds.
+    ///
+    /// Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_parser::Schedule;
+    ///
+    /// let schedule: Schedule = "0 12 * * *".parse().unwrap();
+    /// let noon = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    /// assert_eq!(schedule.next_at_or_after(&noon).unwrap(), noon);
+    /// assert_eq!(
+    ///     schedule.next_after(&noon).unwrap(),
+    ///     Utc.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap()
+    /// );
+    /// ```
+    /// # Errors
+    /// [`ParseError`](enum.ParseError.html)
+    pub fn next_at_or_after<TZ: TimeZone>(
+        &self,
+        dt: &DateTime<TZ>,
+    ) -> Result<DateTime<TZ>, ParseError> {
+        self.next_from(dt, true, self.until_year(dt.year()))?
+            .ok_or(ParseError::HorizonExceeded)
+    }
+
+    /// Find the previous date strictly before `dt`, in the timezone of `dt`
+    ///
+    /// The search gives up after the same number of years as
+    /// [`next_after`](#method.next_after).
+    ///
+    /// Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_parser::Schedule;
+    ///
+    /// let schedule: Schedule = "0 12-18/3 * * *".parse().unwrap();
+    /// let now = Utc.with_ymd_and_hms(2019, 11, 8, 13, 0, 0).unwrap();
+    /// let prev = schedule.prev_before(&now).unwrap();
+    /// assert_eq!(prev, Utc.with_ymd_and_hms(2019, 11, 8, 12, 0, 0).unwrap());
+    /// ```
+    /// # Errors
+    /// [`ParseError`](enum.ParseError.html)
+    pub fn prev_before<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> Result<DateTime<TZ>, ParseError> {
+        self.prev_from(dt, false, self.since_year(dt.year()))?
+            .ok_or(ParseError::HorizonExceeded)
+    }
+
+    /// Find the previous date at or before `dt`, in the timezone of `dt`
+    ///
+    /// Dates are whole minutes, `12:00:30` gives `12:00` for `* * * * *`.
+    ///
+    /// # Errors
+    /// [`ParseError`](enum.ParseError.html)
+    pub fn prev_at_or_before<TZ: TimeZone>(
+        &self,
+        dt: &DateTime<TZ>,
+    ) -> Result<DateTime<TZ>, ParseError> {
+        self.prev_from(dt, true, self.since_year(dt.year()))?
+            .ok_or(ParseError::HorizonExceeded)
+    }
+
+    /// Iterate over the dates within `range`, in the timezone of its start
+    ///
+    /// The start of the range is required, use `start..end` or `start..=end`
+    /// for an inclusive start and `(Bound::Excluded(start), end)` for an
+    /// exclusive one. Without an end, iteration stops once the search horizon
+    /// is exceeded from the previous date.
+    ///
+    /// Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_parser::Schedule;
+    /// use std::ops::Bound;
+    ///
+    /// let schedule: Schedule = "0 */6 * * *".parse().unwrap();
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+    ///
+    /// // [start, end)
+    /// assert_eq!(schedule.occurrences_between(start..end).unwrap().count(), 4);
+    /// // [start, end]
+    /// assert_eq!(schedule.occurrences_between(start..=end).unwrap().count(), 5);
+    /// // (start, end]
+    /// let range = (Bound::Excluded(start), Bound::Included(end));
+    /// assert_eq!(schedule.occurrences_between(range).unwrap().count(), 4);
+    /// ```
+    /// # Errors
+    /// [`ParseError::InvalidRange`](enum.ParseError.html) if the range has no
+    /// start, [`ParseError`](enum.ParseError.html) if the start is not a valid
+    /// date.
+    pub fn occurrences_between<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Occurrences<'_, TZ>, ParseError> {
+        Occurrences::new(self, range)
+    }
+
+    /// Count the dates within `range` without iterating over every one of them
+    ///
+    /// Days whose dates are all within the range are counted at once, only
+    /// the days at the edges of the range or with a DST transition are
+    /// searched date by date.
+    ///
+    /// Example
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use cron_parser::Schedule;
+    ///
+    /// let schedule: Schedule = "*/5 9-17 * * Mon-Fri".parse().unwrap();
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    /// // 262 weekdays, 9 hours, 12 times per hour
+    /// assert_eq!(schedule.count_between(start..end).unwrap(), 262 * 9 * 12);
+    /// ```
+    /// # Errors
+    /// [`ParseError::InvalidRange`](enum.ParseError.html) if the range is not
+    /// bounded on both sides, [`ParseError`](enum.ParseError.html) if the start
+    /// is not a valid date.
+    pub fn count_between<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<u64, ParseError> {
+        let mut occurrences = self.occurrences_between(range)?;
+        if matches!(occurrences.end, Bound::Unbounded) {
+            return Err(ParseError::InvalidRange);
+        }
+
+        let (Some(first_hour), Some(first_minute), Some(last_hour), Some(last_minute)) = (
+            self.hours.first(),
+            self.minutes.first(),
+            self.hours.last(),
+            self.minutes.last(),
+        ) else {
+            return Ok(0);
+        };
+        let per_day = u64::from(self.hours.len()) * u64::from(self.minutes.len());
+
+        let mut count = 0;
+        while let Some(next) = occurrences.next() {
+            count += 1;
+
+            // the first date of a day, if the last one is within the range
+            // and the offset doesn't change, all the dates of the day count
+            let local = next.naive_local();
+            if local.hour() != first_hour || local.minute() != first_minute {
+                continue;
+            }
+            let date = local.date();
+            let (Some(day_start), Some(day_end), Some(last)) = (
+                date.and_hms_opt(0, 0, 0),
+                date.succ_opt().and_then(|d| d.and_hms_opt(0, 0, 0)),
+                date.and_hms_opt(last_hour, last_minute, 0),
+            ) else {
+                continue;
+            };
+            let tz = next.timezone();
+            let same_offset = match (
+                tz.from_local_datetime(&day_start).single(),
+                tz.from_local_datetime(&day_end).single(),
+            ) {
+                (Some(a), Some(b)) => a.offset().fix() == b.offset().fix(),
+                _ => false,
+            };
+            if !same_offset {
+                continue;
+            }
+            if let Some(last) = tz.from_local_datetime(&last).single()
+                && occurrences.before_end(&last)
+            {
+                count += per_day - 1;
+                occurrences.from = Some(last);
+            }
+        }
+
+        Ok(count)
+    }
+
+    // last year within the horizon when searching from `year`
+    fn until_year(&self, year: i32) -> Option<i64> {
+        self.horizon.map(|years| i64::from(year) + i64::from(years))
+    }
+
+    // first year within the horizon when searching back from `year`
+    fn since_year(&self, year: i32) -> Option<i64> {
+        self.horizon.map(|years| i64::from(year) - i64::from(years))
+    }
+
+    // find the first date after `from`, or at it if `inclusive`, until the end
+    // of `until_year` or of the dates chrono can represent
+    fn next_from<TZ: TimeZone>(
+        &self,
+        from: &DateTime<TZ>,
+        inclusive: bool,
+        until_year: Option<i64>,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let tz = from.timezone();
+        let first_minute = |local: &NaiveDateTime| {
+            if inclusive {
+                ceil_minute(local)
+            } else {
+                start_of_minute(local).map(|start| start.checked_add_signed(Duration::minutes(1)))
+            }
+        };
+
+        let local = from.naive_local();
+        let Some(start) = first_minute(&local)? else {
+            return Ok(None);
+        };
+        let mut next = self.search(&tz, start, until_year, from, inclusive)?;
+
+        // during the first of the repeated hours, the second one comes before
+        // the local times that follow and is searched too unless it's skipped
+        if let chrono::LocalResult::Ambiguous(earlier, later) = tz.from_local_datetime(&local)
+            && earlier == *from
+            && self.overlap_policy_for(overlap_shift(&earlier, &later)) != OverlapPolicy::Earlier
+        {
+            let offset = later.offset().fix().local_minus_utc();
+            let local = from
+                .naive_utc()
+                .checked_add_signed(Duration::seconds(i64::from(offset)));
+            if let Some(local) = local
+                && let Some(start) = first_minute(&local)?
+                && let Some(repeated) = self.search(&tz, start, until_year, from, inclusive)?
+            {
+                next = Some(match next {
+                    Some(next) if next < repeated => next,
+                    _ => repeated,
+                });
+            }
+        }
+
+        // after a date shifted out of a gap, the later local times of the gap
+        // are shifted after it too and are searched from the local time it
+        // was shifted from
+        if let Some(day_before) = from.naive_utc().checked_sub_signed(Duration::days(1)) {
+            let offset_before = tz.offset_from_utc_datetime(&day_before).fix();
+            let gap = from.offset().fix().local_minus_utc() - offset_before.local_minus_utc();
+            if gap > 0
+                && let Some(in_gap) = local.checked_sub_signed(Duration::seconds(i64::from(gap)))
+                && matches!(tz.from_local_datetime(&in_gap), chrono::LocalResult::None)
+                && let Some(start) = first_minute(&in_gap)?
+                && let Some(shifted) = self.search(&tz, start, until_year, from, inclusive)?
+            {
+                next = Some(match next {
+                    Some(next) if next < shifted => next,
+                    _ => shifted,
+                });
+            }
+        }
+
+        Ok(next)
+    }
+
+    // search from `next` (a local time, inclusive) until the end of
+    // `until_year` for a date after `from`, or at it if `inclusive`, `None`
+    // past the dates chrono can represent
+    fn search<TZ: TimeZone>(
+        &self,
+        tz: &TZ,
+        mut next: DateTime<Utc>,
+        until_year: Option<i64>,
+        from: &DateTime<TZ>,
+        inclusive: bool,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let accepts =
+            |datetime: &DateTime<TZ>| *datetime > *from || (inclusive && *datetime == *from);
+        let minute = Duration::minutes(1);
+
+        // each step jumps straight to the next value allowed by a field,
+        // resetting the lower fields, so the work per call is bounded
+        let result = loop {
+            if let Some(until_year) = until_year
+                && i64::from(next.year()) > until_year
+            {
+                return Ok(None);
+            }
+
+            // * * * <month> *
+            match self.months.next_from(next.month()) {
+                Some(month) if month == next.month() => {}
+                Some(month) => {
+                    next = make_utc_datetime(next.year(), month, 1, 0, 0, 0)?;
+                }
+                None => {
+                    let month = self.months.first().unwrap_or(1);
+                    let Some(next_year) = first_day_of(next.year() + 1, month) else {
+                        return Ok(None);
+                    };
+                    next = next_year;
+                    continue;
+                }
+            }
+
+            // * * <dom> * <dow>
+            match self.next_day(next.year(), next.month(), next.day()) {
+                Some(day) if day == next.day() => {}
+                Some(day) => {
+                    next = make_utc_datetime(next.year(), next.month(), day, 0, 0, 0)?;
+                }
+                None => {
+                    let Some(next_month) = first_day_of_next_month(next.year(), next.month())
+                    else {
+                        return Ok(None);
+                    };
+                    next = next_month;
+                    continue;
+                }
+            }
+
+            // * <hour> * * *
+            match self.hours.next_from(next.hour()) {
+                Some(hour) if hour == next.hour() => {}
+                Some(hour) => {
+                    next = make_utc_datetime(next.year(), next.month(), next.day(), hour, 0, 0)?;
+                }
+                None => {
+                    let Some(next_day) =
+                        make_utc_datetime(next.year(), next.month(), next.day(), 0, 0, 0)?
+                            .checked_add_signed(Duration::days(1))
+                    else {
+                        return Ok(None);
+                    };
+                    next = next_day;
+                    continue;
+                }
+            }
+
+            // <minute> * * * *
+            match self.minutes.next_from(next.minute()) {
+                Some(minute) if minute == next.minute() => {}
+                Some(minute) => {
+                    next = make_utc_datetime(
+                        next.year(),
+                        next.month(),
+                        next.day(),
+                        next.hour(),
+                        minute,
+                        0,
+                    )?;
+                }
+                None => {
+                    let Some(next_hour) = make_utc_datetime(
+                        next.year(),
+                        next.month(),
+                        next.day(),
+                        next.hour(),
+                        0,
+                        0,
+                    )?
+                    .checked_add_signed(Duration::hours(1)) else {
+                        return Ok(None);
+                    };
+                    next = next_hour;
+                    continue;
+                }
+            }
+
+            // Valid datetime for the timezone
+            let found = self.resolve(tz, &next.naive_local(), accepts, false)?;
+
+            // dates before `from` are possible when the clocks go back
+            match found {
+                Some(datetime) if accepts(&datetime) => break datetime,
+                _ => match next.checked_add_signed(minute) {
+                    Some(after) => next = after,
+                    None => return Ok(None),
+                },
+            }
+        };
+
+        Ok(Some(result))
+    }
+
+    // find the last date before `from`, or at it if `inclusive`, back to the
+    // start of `since_year` or of the dates chrono can represent
+    fn prev_from<TZ: TimeZone>(
+        &self,
+        from: &DateTime<TZ>,
+        inclusive: bool,
+        since_year: Option<i64>,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let tz = from.timezone();
+        let last_minute = |local: &NaiveDateTime| {
+            if inclusive {
+                start_of_minute(local).map(Some)
+            } else {
+                ceil_minute(local)
+                    .map(|ceil| ceil.and_then(|ceil| ceil.checked_sub_signed(Duration::minutes(1))))
+            }
+        };
+
+        let local = from.naive_local();
+        let Some(start) = last_minute(&local)? else {
+            return Ok(None);
+        };
+        let mut prev = self.search_back(&tz, start, since_year, from, inclusive)?;
+
+        // during the second of the repeated hours, the first one comes after
+        // the local times that precede and is searched too unless it's skipped
+        if let chrono::LocalResult::Ambiguous(earlier, later) = tz.from_local_datetime(&local)
+            && later == *from
+            && self.overlap_policy_for(overlap_shift(&earlier, &later)) != OverlapPolicy::Later
+        {
+            let offset = earlier.offset().fix().local_minus_utc();
+            let local = from
+                .naive_utc()
+                .checked_add_signed(Duration::seconds(i64::from(offset)));
+            if let Some(local) = local
+                && let Some(start) = last_minute(&local)?
+                && let Some(repeated) = self.search_back(&tz, start, since_year, from, inclusive)?
+            {
+                prev = Some(match prev {
+                    Some(prev) if prev > repeated => prev,
+                    _ => repeated,
+                });
+            }
+        }
+
+        Ok(prev)
+    }
+
+    // search back from `prev` (a local time, inclusive) until the start of
+    // `since_year` for a date before `from`, or at it if `inclusive`, `None`
+    // before the dates chrono can represent
+    fn search_back<TZ: TimeZone>(
+        &self,
+        tz: &TZ,
+        mut prev: DateTime<Utc>,
+        since_year: Option<i64>,
+        from: &DateTime<TZ>,
+        inclusive: bool,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let accepts =
+            |datetime: &DateTime<TZ>| *datetime < *from || (inclusive && *datetime == *from);
+        let minute = Duration::minutes(1);
+
+        // same as `search` the other way around, each step jumps to the last
+        // minute of the previous value allowed by a field
+        let result = loop {
+            if let Some(since_year) = since_year
+                && i64::from(prev.year()) < since_year
+            {
+                return Ok(None);
+            }
+
+            // * * * <month> *
+            match self.months.prev_from(prev.month()) {
+                Some(month) if month == prev.month() => {}
+                Some(month) => {
+                    let Some(last) = last_minute_of_month(prev.year(), month) else {
+                        return Ok(None);
+                    };
+                    prev = last;
+                }
+                None => {
+                    let month = self.months.last().unwrap_or(12);
+                    let Some(last) = last_minute_of_month(prev.year() - 1, month) else {
+                        return Ok(None);
+                    };
+                    prev = last;
+                    continue;
+                }
+            }
+
+            // * * <dom> * <dow>
+            match self.prev_day(prev.year(), prev.month(), prev.day()) {
+                Some(day) if day == prev.day() => {}
+                Some(day) => {
+                    prev = make_utc_datetime(prev.year(), prev.month(), day, 23, 59, 0)?;
+                }
+                None => {
+                    let Some(last) = make_utc_datetime(prev.year(), prev.month(), 1, 0, 0, 0)?
+                        .checked_sub_signed(minute)
+                    else {
+                        return Ok(None);
+                    };
+                    prev = last;
+                    continue;
+                }
+            }
+
+            // * <hour> * * *
+            match self.hours.prev_from(prev.hour()) {
+                Some(hour) if hour == prev.hour() => {}
+                Some(hour) => {
+                    prev = make_utc_datetime(prev.year(), prev.month(), prev.day(), hour, 59, 0)?;
+                }
+                None => {
+                    let Some(last) =
+                        make_utc_datetime(prev.year(), prev.month(), prev.day(), 0, 0, 0)?
+                            .checked_sub_signed(minute)
+                    else {
+                        return Ok(None);
+                    };
+                    prev = last;
+                    continue;
+                }
+            }
+
+            // <minute> * * * *
+            let start_of_hour =
+                make_utc_datetime(prev.year(), prev.month(), prev.day(), prev.hour(), 0, 0)?;
+            match self.minutes.prev_from(prev.minute()) {
+                Some(minute) if minute == prev.minute() => {}
+                Some(minute) => {
+                    prev = make_utc_datetime(
+                        prev.year(),
+                        prev.month(),
+                        prev.day(),
+                        prev.hour(),
+                        minute,
+                        0,
+                    )?;
+                }
+                None => {
+                    let Some(last) = start_of_hour.checked_sub_signed(minute) else {
+                        return Ok(None);
+                    };
+                    prev = last;
+                    continue;
+                }
+            }
+
+            match self.resolve(tz, &prev.naive_local(), accepts, true)? {
+                Some(datetime) if accepts(&datetime) => break datetime,
+                _ => match prev.checked_sub_signed(minute) {
+                    Some(before) => prev = before,
+                    None => return Ok(None),
+                },
+            }
+        };
+
+        Ok(Some(result))
+    }
+
+    // find the last day from `day` back to the start of the month matching
+    // both the day of month and the day of week
+    fn prev_day(&self, year: i32, month: u32, day: u32) -> Option<u32> {
+        let first_weekday = NaiveDate::from_ymd_opt(year, month, 1)?
+            .weekday()
+            .num_days_from_sunday();
+
+        let mut day = self
+            .days_of_month
+            .prev_from(day.min(days_in_month(year, month)))?;
+        loop {
+            if self.days_of_week.contains((first_weekday + day - 1) % 7) {
+                return Some(day);
+            }
+            day = self.days_of_month.prev_from(day.checked_sub(1)?)?;
+        }
+    }
+
+    // the date of a local time matching every field, following the DST
+    // policies, `accepts` tells which of two repeated dates is usable, the
+    // first one in the direction of the search, forward or `backward`
+    fn resolve<TZ: TimeZone>(
+        &self,
+        tz: &TZ,
+        local: &NaiveDateTime,
+        accepts: impl Fn(&DateTime<TZ>) -> bool,
+        backward: bool,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let datetime = match tz.from_local_datetime(local) {
+            chrono::LocalResult::Single(datetime) => Some(datetime),
+            chrono::LocalResult::Ambiguous(earlier, later) => {
+                let (first, second) = if backward {
+                    (later.clone(), earlier.clone())
+                } else {
+                    (earlier.clone(), later.clone())
+                };
+                match self.overlap_policy_for(overlap_shift(&earlier, &later)) {
+                    OverlapPolicy::Earlier => Some(earlier),
+                    OverlapPolicy::Later => Some(later),
+                    OverlapPolicy::Both if accepts(&first) => Some(first),
+                    OverlapPolicy::Both => Some(second),
+                }
+            }
+            chrono::LocalResult::None => {
+                let shift = if self.vixie_dst {
+                    gap_shift(tz, local)?
+                } else {
+                    0
+                };
+                match self.gap_policy_for(shift) {
+                    GapPolicy::Skip => None,
+                    GapPolicy::FirstValid => Some(nearest_valid(tz, local, Duration::minutes(1))?),
+                    GapPolicy::Shift => {
+                        // interpret the local time with the offset before the gap
+                        let before = nearest_valid(tz, local, Duration::minutes(-1))?;
+                        let offset = before.offset().fix().local_minus_utc();
+                        local
+                            .checked_sub_signed(Duration::seconds(i64::from(offset)))
+                            .map(|utc| tz.from_utc_datetime(&utc))
+                    }
+                }
+            }
+        };
+        Ok(datetime)
+    }
+}
+
+impl Recurrence for Schedule {
+    fn first_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (from, inclusive) = match range.start_bound() {
+            Bound::Included(start) => (start, true),
+            Bound::Excluded(start) => (start, false),
+            Bound::Unbounded => return Err(ParseError::InvalidRange),
+        };
+        let until_year = match range.end_bound() {
+            Bound::Included(end) | Bound::Excluded(end) => Some(i64::from(end.year())),
+            Bound::Unbounded => self.until_year(from.year()),
+        };
+
+        Ok(self
+            .next_from(from, inclusive, until_year)?
+            .filter(|next| range.contains(next)))
+    }
+
+    fn last_in<TZ: TimeZone, R: RangeBounds<DateTime<TZ>>>(
+        &self,
+        range: R,
+    ) -> Result<Option<DateTime<TZ>>, ParseError> {
+        let (from, inclusive) = match range.end_bound() {
+            Bound::Included(end) => (end, true),
+            Bound::Excluded(end) => (end, false),
+            Bound::Unbounded => return Err(ParseError::InvalidRange),
+        };
+        let since_year = match range.start_bound() {
+            Bound::Included(start) | Bound::Excluded(start) => Some(i64::from(start.year())),
+            Bound::Unbounded => self.since_year(from.year()),
+        };
+
+        Ok(self
+            .prev_from(from, inclusive, since_year)?
+            .filter(|prev| range.contains(prev)))
+    }
+
+    // a date the search returns, unlike the inherent method it follows the
+    // DST policies and only matches whole minutes, so combinators don't
+    // return dates the schedule never fires at
+    fn matches<TZ: TimeZone>(&self, dt: &DateTime<TZ>) -> bool {
+        dt.second() == 0
+            && dt.nanosecond() == 0
+            && self
+                .first_in(dt.clone()..=dt.clone())
+                .is_ok_and(|found| found.as_ref() == Some(dt))
+    }
+
+    fn horizon(&self) -> Option<u32> {
+        self.horizon
+    }
+}
+
+// helper function walking from a local time that doesn't exist, one `step` at
+// a time, to the nearest one that does, gaps are never longer than two days
+fn nearest_valid<TZ: TimeZone>(
+    tz: &TZ,
+    local: &NaiveDateTime,
+    step: Duration,
+) -> Result<DateTime<TZ>, ParseError> {
+    let mut local = *local;
+    for _ in 0..2 * 24 * 60 {
+        let Some(next) = local.checked_add_signed(step) else {
+            break;
+        };
+        local = next;
+        match tz.from_local_datetime(&local) {
+            chrono::LocalResult::Single(datetime) => return Ok(datetime),
+            chrono::LocalResult::Ambiguous(earlier, _later) => return Ok(earlier),
+            chrono::LocalResult::None => {}
+        }
+    }
+    Err(ParseError::InvalidTimezone)
+}
+
+// helper function returning the length in seconds of the gap containing `local`
+fn gap_shift<TZ: TimeZone>(tz: &TZ, local: &NaiveDateTime) -> Result<i32, ParseError> {
+    let before = nearest_valid(tz, local, Duration::minutes(-1))?;
+    let after = nearest_valid(tz, local, Duration::minutes(1))?;
+    Ok(after.offset().fix().local_minus_utc() - before.offset().fix().local_minus_utc())
+}
+
+// helper function returning how long in seconds a repeated local time is repeated for
+fn overlap_shift<TZ: TimeZone>(earlier: &DateTime<TZ>, later: &DateTime<TZ>) -> i32 {
+    earlier.offset().fix().local_minus_utc() - later.offset().fix().local_minus_utc()
+}
+
+// helper function truncating a local time to the minute
+fn start_of_minute(local: &NaiveDateTime) -> Result<DateTime<Utc>, ParseError> {
+    make_utc_datetime(
+        local.year(),
+        local.month(),
+        local.day(),
+        local.hour(),
+        local.minute(),
+        0,
+    )
+}
+
+// helper function rounding a local time up to the minute, `None` past the
+// dates chrono can represent
+fn ceil_minute(local: &NaiveDateTime) -> Result<Option<DateTime<Utc>>, ParseError> {
+    let start = start_of_minute(local)?;
+    if start.naive_utc() == *local {
+        Ok(Some(start))
+    } else {
+        Ok(start.checked_add_signed(Duration::minutes(1)))
+    }
+}
+
+// helper function returning the number of days in a month
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if NaiveDate::from_ymd_opt(year, 2, 29).is_some() => 29,
+        2 => 28,
+        _ => max_days_in_month(month),
+    }
+}
+
+// helper function returning midnight of the first day of a month, `None` past
+// the dates chrono can represent
+fn first_day_of(year: i32, month: u32) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()
+}
+
+// helper function returning midnight of the first day of the following month
+fn first_day_of_next_month(year: i32, month: u32) -> Option<DateTime<Utc>> {
+    if month == 12 {
+        first_day_of(year + 1, 1)
+    } else {
+        first_day_of(year, month + 1)
+    }
+}
+
+// helper function returning the last minute of a month, `None` before the
+// dates chrono can represent
+fn last_minute_of_month(year: i32, month: u32) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(year, month, days_in_month(year, month), 23, 59, 0)
+        .single()
+}
+
+// helper function returning the longest a month can be, counting leap years
+const fn max_days_in_month(month: u32) -> u32 {
+    match month {
+        2 => 29,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// errors found while compiling, all of them when allocating, otherwise only the first
+#[derive(Default)]
+pub(crate) struct FieldErrors {
+    #[cfg(feature = "alloc")]
+    errors: Vec<FieldError>,
+    #[cfg(not(feature = "alloc"))]
+    first: Option<FieldError>,
+}
+
+impl FieldErrors {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn push(&mut self, error: FieldError) {
+        self.errors.push(error);
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn push(&mut self, error: FieldError) {
+        self.first.get_or_insert(error);
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn into_result(self) -> Result<(), ParseError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ParseError::InvalidFields(self.errors))
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn into_result(self) -> Result<(), ParseError> {
+        self.first.map_or(Ok(()), |first| Err(first.error))
+    }
+}
@@ -1,9 +1,14 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::panic)]
 use chrono::{Datelike, TimeZone, Timelike, Utc};
-use chrono_tz::{America::Chicago, US::Pacific};
-use cron_parser::{parse, parse_field};
-use std::collections::BTreeSet;
+use chrono_tz::{
+    America::Chicago, Australia::Lord_Howe, Europe::London, Pacific::Apia, US::Pacific,
+};
+use cron_parser::{
+    BusinessDay, BusinessDays, Calendar, Field, FieldSet, GapPolicy, Interval, Locale,
+    OverlapPolicy, ParseError, Recurrence, Schedule, parse, parse_field, validate,
+};
+use std::{collections::BTreeSet, ops::Bound};
 
 macro_rules! parse_field_tests {
     ($($name:ident: $value:expr,)*) => {
@@ -409,9 +414,9 @@ fn test_mixed_dow_formats() {
 #[test]
 fn test_cron_edge_cases() {
     // Test some edge cases with full cron expressions
-    // April only has 30 days, so April 31 will error after 4-year lookahead
+    // April only has 30 days, so April 31 never matches
     assert!(parse("0 0 31 4 *", &Utc::now()).is_err());
-    // Feb 31 doesn't exist, will error after 4-year lookahead
+    // Feb 31 doesn't exist, never matches
     assert!(parse("0 0 31 2 *", &Utc::now()).is_err());
 
     // Invalid: step in dow field
@@ -462,6 +467,42 @@ fn test_cron_never_matches() {
     assert!(parse("0 0 30 2 *", &now).is_err());
 }
 
+#[test]
+fn test_never_matches_is_not_a_syntax_error() {
+    let now = Utc.timestamp_opt(1_573_151_292, 0).unwrap();
+    assert!(matches!(
+        parse("0 0 30 2 *", &now),
+        Err(ParseError::NeverMatches)
+    ));
+    assert!(matches!(
+        parse("0 0 31 4,6,9,11 *", &now),
+        Err(ParseError::NeverMatches)
+    ));
+    assert!(matches!(
+        parse("0 0 30-31 2 *", &now),
+        Err(ParseError::NeverMatches)
+    ));
+    // detected without searching
+    assert!(matches!(
+        validate("0 0 31 2 *"),
+        Err(ParseError::NeverMatches)
+    ));
+    assert!(matches!(
+        "0 0 31 2,4 *".parse::<Schedule>(),
+        Err(ParseError::NeverMatches)
+    ));
+
+    // a single month with the day is enough
+    assert!(parse("0 0 31 2,4,5 *", &now).is_ok());
+    assert!(parse("0 0 29-31 2 *", &now).is_ok());
+
+    // syntax errors win over impossible dates
+    assert!(matches!(
+        parse("0 0 31 2 Foo", &now),
+        Err(ParseError::InvalidFields(_))
+    ));
+}
+
 // Test with DST transition (spring forward) - skipped hour
 #[test]
 fn test_dst_spring_forward_skipped_time() {
@@ -515,5 +556,2325 @@ fn test_very_restrictive_cron() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_validate_ok() {
+    assert!(validate("*/5 * * * *").is_ok());
+    assert!(validate("0 12-18/3 * * Mon-Fri").is_ok());
+    // Feb 29 exists in leap years
+    assert!(validate("0 0 29 2 *").is_ok());
+}
+
+#[test]
+fn test_validate_field_count() {
+    assert!(matches!(
+        validate("*/5 * * *"),
+        Err(ParseError::InvalidCron)
+    ));
+    assert!(matches!(
+        validate("* * * * * *"),
+        Err(ParseError::InvalidCron)
+    ));
+}
+
+#[test]
+fn test_validate_collects_all_errors() {
+    let Err(ParseError::InvalidFields(errors)) = validate("60 24 0 13 Foo") else {
+        panic!("expected InvalidFields");
+    };
+    let fields: Vec<Field> = errors.iter().map(|e| e.field).collect();
+    assert_eq!(fields, Field::ALL.to_vec());
+}
+
+#[test]
+fn test_validate_reports_only_bad_fields() {
+    let Err(ParseError::InvalidFields(errors)) = validate("*/0 * 1-40 * 8-5") else {
+        panic!("expected InvalidFields");
+    };
+    let fields: Vec<Field> = errors.iter().map(|e| e.field).collect();
+    assert_eq!(
+        fields,
+        vec![Field::Minute, Field::DayOfMonth, Field::DayOfWeek]
+    );
+    assert!(matches!(
+        errors.first().map(|e| &e.error),
+        Some(ParseError::InvalidValue)
+    ));
+}
+
+#[test]
+fn test_validate_error_display() {
+    let err = validate("60 * * * Foo").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid fields: minute: invalid value, day of week: invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_parse_validates_before_searching() {
+    // the month never matches the day, but the bad minute must be reported
+    let now = Utc.timestamp_opt(1_573_151_292, 0).unwrap();
+    let Err(ParseError::InvalidFields(errors)) = parse("60 0 30 2 *", &now) else {
+        panic!("expected InvalidFields");
+    };
+    let fields: Vec<Field> = errors.iter().map(|e| e.field).collect();
+    assert_eq!(fields, vec![Field::Minute]);
+
+    // a bad day of week is reported even if the minute never matches first
+    let Err(ParseError::InvalidFields(errors)) = parse("0 0 31 4 Foo", &now) else {
+        panic!("expected InvalidFields");
+    };
+    let fields: Vec<Field> = errors.iter().map(|e| e.field).collect();
+    assert_eq!(fields, vec![Field::DayOfWeek]);
+}
+
+#[test]
+fn test_parse_error_independent_of_start_time() {
+    for ts in [0, 1_573_151_292, 1_893_456_000, 4_102_444_800] {
+        let dt = Utc.timestamp_opt(ts, 0).unwrap();
+        let err = parse("*/5 24 * 2 *", &dt).unwrap_err();
+        assert_eq!(err.to_string(), "invalid fields: hour: invalid value");
+    }
+}
+
+#[test]
+fn test_schedule_from_str() {
+    let schedule: Schedule = "0 23 */2 * *".parse().unwrap();
+    let now = Utc.timestamp_opt(1_573_239_864, 0).unwrap();
+    assert_eq!(
+        schedule.next_after(&now).unwrap(),
+        parse("0 23 */2 * *", &now).unwrap()
+    );
+    assert!("0 23 */2 *".parse::<Schedule>().is_err());
+    assert!("0 23 */0 * *".parse::<Schedule>().is_err());
+}
+
+#[test]
+fn test_default_horizon() {
+    let now = Utc.timestamp_opt(1_577_836_800, 0).unwrap(); // 2020-01-01
+    assert!(matches!(
+        parse("0 0 29 2 0", &now),
+        Err(ParseError::HorizonExceeded)
+    ));
+    let schedule: Schedule = "0 0 29 2 0".parse().unwrap();
+    assert!(matches!(
+        schedule.next_after(&now),
+        Err(ParseError::HorizonExceeded)
+    ));
+}
+
+#[test]
+fn test_custom_horizon() {
+    let now = Utc.timestamp_opt(1_577_836_800, 0).unwrap(); // 2020-01-01
+    let schedule: Schedule = "0 0 29 2 0".parse().unwrap();
+
+    // 2032-02-29 is the next Feb 29 on a Sunday
+    let expected = Utc.with_ymd_and_hms(2032, 2, 29, 0, 0, 0).unwrap();
+    assert!(schedule.clone().with_horizon(11).next_after(&now).is_err());
+    assert_eq!(
+        schedule.clone().with_horizon(12).next_after(&now).unwrap(),
+        expected
+    );
+    assert_eq!(
+        schedule.without_horizon().next_after(&now).unwrap(),
+        expected
+    );
+
+    // a zero horizon only searches the current year
+    let schedule: Schedule = "0 0 1 1 *".parse().unwrap();
+    assert!(matches!(
+        schedule.clone().with_horizon(0).next_after(&now),
+        Err(ParseError::HorizonExceeded)
+    ));
+    assert_eq!(
+        schedule.with_horizon(1).next_after(&now).unwrap(),
+        Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_without_horizon_far_match() {
+    // Feb 29 on a Monday after 2096 skips the non leap year 2100
+    let now = Utc.with_ymd_and_hms(2097, 1, 1, 0, 0, 0).unwrap();
+    let schedule: Schedule = "0 0 29 2 1".parse().unwrap();
+    let next = schedule.without_horizon().next_after(&now).unwrap();
+    assert_eq!(next.month(), 2);
+    assert_eq!(next.day(), 29);
+    assert_eq!(next.weekday(), chrono::Weekday::Mon);
+    assert!(next.year() > 2100);
+}
+
+// check every field of `cron` against a UTC date, without `Schedule`
+fn brute_force_matcher(cron: &str) -> impl Fn(&chrono::DateTime<Utc>) -> bool {
+    let fields: Vec<BTreeSet<u32>> = cron
+        .split_whitespace()
+        .zip([(0, 59), (0, 23), (1, 31), (1, 12), (0, 6)])
+        .map(|(field, (min, max))| parse_field(field, min, max).unwrap())
+        .collect();
+    let [minutes, hours, days_of_month, months, days_of_week] = fields.as_slice() else {
+        panic!("expected 5 fields");
+    };
+    let (minutes, hours, days_of_month, months, days_of_week) = (
+        minutes.clone(),
+        hours.clone(),
+        days_of_month.clone(),
+        months.clone(),
+        days_of_week.clone(),
+    );
+
+    move |dt| {
+        minutes.contains(&dt.minute())
+            && hours.contains(&dt.hour())
+            && days_of_month.contains(&dt.day())
+            && months.contains(&dt.month())
+            && days_of_week.contains(&dt.weekday().num_days_from_sunday())
+    }
+}
+
+// reference implementation checking every minute, only usable for schedules
+// that match often enough
+fn brute_force_next(cron: &str, dt: &chrono::DateTime<Utc>) -> chrono::DateTime<Utc> {
+    let matches = brute_force_matcher(cron);
+    let mut next = Utc
+        .with_ymd_and_hms(dt.year(), dt.month(), dt.day(), dt.hour(), dt.minute(), 0)
+        .unwrap()
+        + chrono::Duration::minutes(1);
+    while !matches(&next) {
+        next += chrono::Duration::minutes(1);
+    }
+    next
+}
+
+fn brute_force_prev(cron: &str, dt: &chrono::DateTime<Utc>) -> chrono::DateTime<Utc> {
+    let matches = brute_force_matcher(cron);
+    let mut prev = Utc
+        .with_ymd_and_hms(dt.year(), dt.month(), dt.day(), dt.hour(), dt.minute(), 0)
+        .unwrap();
+    if prev == *dt {
+        prev -= chrono::Duration::minutes(1);
+    }
+    while !matches(&prev) {
+        prev -= chrono::Duration::minutes(1);
+    }
+    prev
+}
+
+#[test]
+fn test_search_matches_brute_force() {
+    let crons = [
+        "* * * * *",
+        "*/7 * * * *",
+        "59 23 * * *",
+        "0 0 * * Sun",
+        "* * * * 1",
+        "30 */5 * * Mon-Fri",
+        "15,45 9-17 * * 1-5",
+        "0 0 1,15 * *",
+        "5 4 31 * *",
+        "*/20 1-3 * * 6",
+        "0 12-18/3 * * *",
+        "1-2 0 * * Wed,Fri",
+        "0 0 29 2-3 *",
+    ];
+    let starts = [
+        1_572_969_395, // 2019-11-05 15:56:35
+        1_577_836_799, // 2019-12-31 23:59:59
+        1_582_934_340, // 2020-02-28 23:59:00
+        1_709_251_199, // 2024-02-29 23:59:59
+        1_711_929_600, // 2024-04-01 00:00:00
+    ];
+    for cron in crons {
+        let schedule: Schedule = cron.parse().unwrap();
+        for ts in starts {
+            let dt = Utc.timestamp_opt(ts, 0).unwrap();
+            assert_eq!(
+                schedule.next_after(&dt).unwrap(),
+                brute_force_next(cron, &dt),
+                "{cron} from {dt}"
+            );
+            assert_eq!(
+                schedule.prev_before(&dt).unwrap(),
+                brute_force_prev(cron, &dt),
+                "{cron} back from {dt}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_dow_resets_time_of_day() {
+    // Sunday 2019-11-10 12:00, the first minute on Monday is midnight
+    let now = Utc.with_ymd_and_hms(2019, 11, 10, 12, 0, 0).unwrap();
+    assert_eq!(
+        parse("* * * * Mon", &now).unwrap(),
+        Utc.with_ymd_and_hms(2019, 11, 11, 0, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_sparse_schedules() {
+    let now = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(
+        parse("59 23 31 12 *", &now).unwrap(),
+        Utc.with_ymd_and_hms(2020, 12, 31, 23, 59, 0).unwrap()
+    );
+    let now = Utc.with_ymd_and_hms(2020, 12, 31, 23, 59, 0).unwrap();
+    assert_eq!(
+        parse("59 23 31 12 *", &now).unwrap(),
+        Utc.with_ymd_and_hms(2021, 12, 31, 23, 59, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_empty_field_is_invalid() {
+    let now = Utc::now();
+    let cases = [
+        (",,, * * * *", vec![Field::Minute]),
+        ("* * , * *", vec![Field::DayOfMonth]),
+        ("* * * , *", vec![Field::Month]),
+        ("0 0 * * ,", vec![Field::DayOfWeek]),
+        (", , * * *", vec![Field::Minute, Field::Hour]),
+    ];
+    for (cron, expected) in cases {
+        let Err(ParseError::InvalidFields(errors)) = cron.parse::<Schedule>() else {
+            panic!("expected InvalidFields for {cron}");
+        };
+        let fields: Vec<Field> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, expected, "{cron}");
+        assert!(
+            errors
+                .iter()
+                .all(|e| matches!(e.error, ParseError::InvalidValue)),
+            "{cron}"
+        );
+        assert!(matches!(
+            parse(cron, &now),
+            Err(ParseError::InvalidFields(_))
+        ));
+    }
+
+    // empty items next to values are ignored
+    assert!(",,5,, * * * *".parse::<Schedule>().is_ok());
+}
+
+#[test]
+fn test_field_set_matches_parse_field() {
+    let fields = [
+        ("*", 0, 59),
+        ("*/5", 0, 59),
+        ("1/6", 0, 23),
+        ("12-18/2", 0, 23),
+        ("5-40/3", 0, 59),
+        ("2-3,9,*/15,1-8,11,9,4,5", 0, 23),
+        ("*/30,40-45,57,30,44,41-45", 0, 59),
+        ("1-31/5", 1, 31),
+        ("Mon-Fri", 0, 6),
+        ("0,Mon,5,Fri", 0, 6),
+        ("1,,,2", 0, 59),
+        (",,,", 0, 59),
+        ("63", 0, 63),
+    ];
+    for (field, min, max) in fields {
+        let set = FieldSet::parse(field, min, max).unwrap();
+        assert_eq!(
+            BTreeSet::from(set),
+            parse_field(field, min, max).unwrap(),
+            "{field}"
+        );
+        assert_eq!(
+            set.len() as usize,
+            parse_field(field, min, max).unwrap().len()
+        );
+    }
+
+    for (field, min, max) in [
+        ("*/0", 0, 59),
+        ("60", 0, 59),
+        ("8-5", 0, 6),
+        ("1/2/3", 0, 59),
+    ] {
+        assert!(FieldSet::parse(field, min, max).is_err(), "{field}");
+    }
+}
+
+#[test]
+fn test_field_set_max() {
+    // parse_field supports larger ranges, FieldSet is limited to 64 values
+    assert!(parse_field("1024", 0, 1024).is_ok());
+    assert!(matches!(
+        FieldSet::parse("1024", 0, 1024),
+        Err(ParseError::InvalidRange)
+    ));
+
+    let mut set = FieldSet::new();
+    set.insert(FieldSet::MAX);
+    set.insert(FieldSet::MAX + 1);
+    assert_eq!(set.len(), 1);
+    assert!(set.contains(63));
+    assert!(!set.contains(64));
+    assert_eq!(set.next_from(0), Some(63));
+    assert_eq!(set.next_from(64), None);
+    assert_eq!(set.prev_from(64), Some(63));
+}
+
+#[test]
+fn test_field_set_lookup() {
+    let set: FieldSet = [1, 7, 13, 19].into_iter().collect();
+    assert_eq!(set.first(), Some(1));
+    assert_eq!(set.next_from(0), Some(1));
+    assert_eq!(set.next_from(7), Some(7));
+    assert_eq!(set.next_from(8), Some(13));
+    assert_eq!(set.next_from(20), None);
+    assert_eq!(set.last(), Some(19));
+    assert_eq!(set.prev_from(63), Some(19));
+    assert_eq!(set.prev_from(12), Some(7));
+    assert_eq!(set.prev_from(1), Some(1));
+    assert_eq!(set.prev_from(0), None);
+    assert!(set.contains(13));
+    assert!(!set.contains(14));
+    assert_eq!(set.iter().collect::<Vec<u32>>(), vec![1, 7, 13, 19]);
+    assert_eq!(format!("{set:?}"), "{1, 7, 13, 19}");
+
+    let empty = FieldSet::new();
+    assert!(empty.is_empty());
+    assert_eq!(empty.first(), None);
+    assert_eq!(empty.last(), None);
+    assert_eq!(empty.iter().next(), None);
+}
+
+#[test]
+fn test_matches_agrees_with_search() {
+    let start = Utc.with_ymd_and_hms(2019, 11, 8, 0, 0, 0).unwrap();
+    for cron in [
+        "*/15 9-17 * * Mon-Fri",
+        "0 0 * * Sun",
+        "30 */5 8-10 * *",
+        "0 12-18/3 * 11 *",
+    ] {
+        let schedule: Schedule = cron.parse().unwrap();
+        // every minute for four days
+        for minute in 0..4 * 24 * 60 {
+            let dt = start + chrono::Duration::minutes(minute);
+            let previous = dt - chrono::Duration::minutes(1);
+            assert_eq!(
+                schedule.matches(&dt),
+                schedule.next_after(&previous).unwrap() == dt,
+                "{cron} at {dt}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_matches_ignores_seconds() {
+    let schedule: Schedule = "0 12 * * *".parse().unwrap();
+    let dt = Utc.with_ymd_and_hms(2019, 11, 8, 12, 0, 59).unwrap();
+    assert!(schedule.matches(&dt));
+    assert!(schedule.matches(&(dt + chrono::Duration::milliseconds(999))));
+    assert!(!schedule.matches(&(dt + chrono::Duration::seconds(1))));
+}
+
+#[test]
+fn test_matches_dom_and_dow() {
+    // Feb 29 that is also a Saturday
+    let schedule: Schedule = "0 0 29 2 6".parse().unwrap();
+    assert!(schedule.matches(&Utc.with_ymd_and_hms(2020, 2, 29, 0, 0, 0).unwrap()));
+    assert!(!schedule.matches(&Utc.with_ymd_and_hms(2024, 2, 29, 0, 0, 0).unwrap()));
+}
+
+#[test]
+fn test_matches_local_time() {
+    // 09:00 in Pacific is 17:00 UTC in winter
+    let schedule: Schedule = "0 9 * * *".parse().unwrap();
+    let utc = Utc.with_ymd_and_hms(2019, 11, 8, 17, 0, 0).unwrap();
+    assert!(!schedule.matches(&utc));
+    assert!(schedule.matches(&utc.with_timezone(&Pacific)));
+}
+
+#[test]
+fn test_occurrences_between_bounds() {
+    let schedule: Schedule = "0 */6 * * *".parse().unwrap();
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+
+    let all: Vec<_> = schedule.occurrences_between(start..=end).unwrap().collect();
+    assert_eq!(all.len(), 5);
+    assert_eq!(all.first(), Some(&start));
+    assert_eq!(all.last(), Some(&end));
+
+    let count = |range: (Bound<_>, Bound<_>)| schedule.occurrences_between(range).unwrap().count();
+    assert_eq!(count((Bound::Included(start), Bound::Excluded(end))), 4);
+    assert_eq!(count((Bound::Excluded(start), Bound::Included(end))), 4);
+    assert_eq!(count((Bound::Excluded(start), Bound::Excluded(end))), 3);
+    assert_eq!(count((Bound::Included(end), Bound::Included(end))), 1);
+    assert_eq!(count((Bound::Excluded(end), Bound::Included(start))), 0);
+
+    assert!(matches!(
+        schedule.occurrences_between(..end),
+        Err(ParseError::InvalidRange)
+    ));
+}
+
+#[test]
+fn test_occurrences_between_sub_minute_start() {
+    let schedule: Schedule = "* * * * *".parse().unwrap();
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 30).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 1, 12, 3, 0).unwrap();
+    let all: Vec<_> = schedule.occurrences_between(start..end).unwrap().collect();
+    assert_eq!(
+        all,
+        vec![
+            Utc.with_ymd_and_hms(2024, 1, 1, 12, 1, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 12, 2, 0).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn test_occurrences_between_matches_next_after() {
+    let schedule: Schedule = "*/20 1-3 * * 6".parse().unwrap();
+    let start = Utc.with_ymd_and_hms(2019, 11, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2020, 2, 1, 0, 0, 0).unwrap();
+
+    let mut expected = Vec::new();
+    let mut next = schedule.next_after(&start).unwrap();
+    while next < end {
+        expected.push(next);
+        next = schedule.next_after(&next).unwrap();
+    }
+    let all: Vec<_> = schedule.occurrences_between(start..end).unwrap().collect();
+    assert_eq!(all, expected);
+}
+
+#[test]
+fn test_occurrences_between_is_bounded_by_the_range() {
+    // the next Feb 29 on a Sunday is beyond the default horizon, but the
+    // range only needs to search until its end
+    let schedule: Schedule = "0 0 29 2 0".parse().unwrap();
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2040, 1, 1, 0, 0, 0).unwrap();
+    let all: Vec<_> = schedule.occurrences_between(start..end).unwrap().collect();
+    assert_eq!(
+        all,
+        vec![Utc.with_ymd_and_hms(2032, 2, 29, 0, 0, 0).unwrap()]
+    );
+
+    let end = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(schedule.count_between(start..end).unwrap(), 0);
+}
+
+#[test]
+fn test_occurrences_unbounded_end() {
+    let schedule: Schedule = "0 0 1 1 *".parse().unwrap();
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let years: Vec<i32> = schedule
+        .occurrences_between(start..)
+        .unwrap()
+        .take(3)
+        .map(|dt| dt.year())
+        .collect();
+    assert_eq!(years, vec![2020, 2021, 2022]);
+
+    // stops once the horizon is exceeded from the previous date
+    let schedule: Schedule = "0 0 29 2 0".parse().unwrap();
+    assert_eq!(schedule.occurrences_between(start..).unwrap().count(), 0);
+    assert!(matches!(
+        schedule.count_between(start..),
+        Err(ParseError::InvalidRange)
+    ));
+}
+
+#[test]
+fn test_count_between_matches_iteration() {
+    let crons = [
+        "* * * * *",
+        "*/15 * * * *",
+        "30 2 * * *",
+        "*/10 1 * * *",
+        "0 9-17 * * Mon-Fri",
+        "0 0 29 2 *",
+        "5 4 31 * *",
+    ];
+    let ranges = [
+        // 2018-11-04 fall back and 2019-03-10 spring forward in Chicago
+        ((2018, 11, 1, 13, 7, 30), (2019, 3, 15, 4, 0, 0)),
+        ((2019, 12, 31, 23, 59, 0), (2020, 3, 1, 0, 0, 0)),
+        ((2024, 1, 1, 0, 0, 0), (2024, 1, 1, 0, 0, 0)),
+    ];
+    for cron in crons {
+        let schedule: Schedule = cron.parse().unwrap();
+        for ((y1, m1, d1, h1, i1, s1), (y2, m2, d2, h2, i2, s2)) in ranges {
+            let start = Chicago.with_ymd_and_hms(y1, m1, d1, h1, i1, s1).unwrap();
+            let end = Chicago.with_ymd_and_hms(y2, m2, d2, h2, i2, s2).unwrap();
+            assert_eq!(
+                schedule.count_between(start..end).unwrap(),
+                schedule.occurrences_between(start..end).unwrap().count() as u64,
+                "{cron} [{start}, {end})"
+            );
+            assert_eq!(
+                schedule.count_between(start..=end).unwrap(),
+                schedule.occurrences_between(start..=end).unwrap().count() as u64,
+                "{cron} [{start}, {end}]"
+            );
+            let start = start.with_timezone(&Utc);
+            let end = end.with_timezone(&Utc);
+            assert_eq!(
+                schedule.count_between(start..end).unwrap(),
+                schedule.occurrences_between(start..end).unwrap().count() as u64,
+                "{cron} [{start}, {end}) UTC"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_next_at_or_after() {
+    let schedule: Schedule = "*/15 * * * *".parse().unwrap();
+    let on_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 15, 0).unwrap();
+    assert_eq!(schedule.next_at_or_after(&on_time).unwrap(), on_time);
+    assert_eq!(
+        schedule.next_after(&on_time).unwrap(),
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 30, 0).unwrap()
+    );
+
+    // the minute has already started
+    let late = on_time + chrono::Duration::seconds(1);
+    assert_eq!(
+        schedule.next_at_or_after(&late).unwrap(),
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 30, 0).unwrap()
+    );
+    let late = on_time + chrono::Duration::nanoseconds(1);
+    assert_eq!(
+        schedule.next_at_or_after(&late).unwrap(),
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 30, 0).unwrap()
+    );
+
+    // not a date of the schedule
+    let early = Utc.with_ymd_and_hms(2024, 1, 1, 12, 14, 59).unwrap();
+    assert_eq!(schedule.next_at_or_after(&early).unwrap(), on_time);
+
+    // in another timezone
+    let pacific = on_time.with_timezone(&Pacific);
+    assert_eq!(schedule.next_at_or_after(&pacific).unwrap(), pacific);
+}
+
+#[test]
+fn test_next_at_or_after_horizon() {
+    let schedule: Schedule = "0 0 29 2 0".parse().unwrap();
+    let now = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    assert!(matches!(
+        schedule.next_at_or_after(&now),
+        Err(ParseError::HorizonExceeded)
+    ));
+    let feb_29 = Utc.with_ymd_and_hms(2032, 2, 29, 0, 0, 0).unwrap();
+    assert_eq!(schedule.next_at_or_after(&feb_29).unwrap(), feb_29);
+}
+
+#[test]
+fn test_sub_minute_start() {
+    let schedule: Schedule = "* * * * *".parse().unwrap();
+    let minute = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    let next_minute = Utc.with_ymd_and_hms(2024, 1, 1, 12, 1, 0).unwrap();
+
+    for offset in [
+        chrono::Duration::zero(),
+        chrono::Duration::nanoseconds(1),
+        chrono::Duration::milliseconds(500),
+        chrono::Duration::seconds(30),
+        chrono::Duration::nanoseconds(59_999_999_999),
+    ] {
+        let dt = minute + offset;
+        assert_eq!(schedule.next_after(&dt).unwrap(), next_minute, "{dt}");
+        assert_eq!(parse("* * * * *", &dt).unwrap(), next_minute, "{dt}");
+        assert!(schedule.matches(&dt), "{dt}");
+        let expected = if offset.is_zero() {
+            minute
+        } else {
+            next_minute
+        };
+        assert_eq!(schedule.next_at_or_after(&dt).unwrap(), expected, "{dt}");
+    }
+}
+
+#[test]
+fn test_results_are_whole_minutes() {
+    let dt =
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 30).unwrap() + chrono::Duration::nanoseconds(123);
+    for cron in ["* * * * *", "*/5 * * * *", "0 0 * * Sun", "0 0 29 2 *"] {
+        let schedule: Schedule = cron.parse().unwrap();
+        let dt = dt.with_timezone(&Pacific);
+        for next in [
+            schedule.next_after(&dt).unwrap(),
+            schedule.next_at_or_after(&dt).unwrap(),
+        ] {
+            assert_eq!(next.second(), 0, "{cron}");
+            assert_eq!(next.nanosecond(), 0, "{cron}");
+        }
+    }
+}
+
+#[test]
+fn test_leap_second_start() {
+    // 2016-12-31 23:59:60 UTC
+    let leap = chrono::NaiveDate::from_ymd_opt(2016, 12, 31)
+        .unwrap()
+        .and_hms_milli_opt(23, 59, 59, 1_500)
+        .unwrap()
+        .and_utc();
+    let schedule: Schedule = "* * * * *".parse().unwrap();
+    let new_year = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(schedule.next_after(&leap).unwrap(), new_year);
+    assert_eq!(schedule.next_at_or_after(&leap).unwrap(), new_year);
+}
+
+#[test]
+fn test_gap_policy_fixed_time() {
+    // 2024-03-10 02:00 - 02:59 doesn't exist in Pacific time
+    let before = Pacific.with_ymd_and_hms(2024, 3, 10, 1, 0, 0).unwrap();
+    let schedule: Schedule = "30 2 * * *".parse().unwrap();
+
+    let next = |policy| {
+        schedule
+            .clone()
+            .with_gap_policy(policy)
+            .next_after(&before)
+            .unwrap()
+    };
+    assert_eq!(
+        next(GapPolicy::Skip),
+        Pacific.with_ymd_and_hms(2024, 3, 11, 2, 30, 0).unwrap()
+    );
+    assert_eq!(
+        next(GapPolicy::FirstValid),
+        Pacific.with_ymd_and_hms(2024, 3, 10, 3, 0, 0).unwrap()
+    );
+    assert_eq!(
+        next(GapPolicy::Shift),
+        Pacific.with_ymd_and_hms(2024, 3, 10, 3, 30, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_gap_policy_runs_once_per_gap() {
+    let before = Chicago.with_ymd_and_hms(2019, 3, 10, 1, 30, 0).unwrap();
+    let end = Chicago.with_ymd_and_hms(2019, 3, 10, 4, 0, 0).unwrap();
+    for policy in [GapPolicy::Skip, GapPolicy::FirstValid, GapPolicy::Shift] {
+        let schedule = "*/15 * * * *"
+            .parse::<Schedule>()
+            .unwrap()
+            .with_gap_policy(policy);
+        let hours: Vec<(u32, u32)> = schedule
+            .occurrences_between(before..end)
+            .unwrap()
+            .map(|dt| (dt.hour(), dt.minute()))
+            .collect();
+        assert_eq!(
+            hours,
+            vec![(1, 30), (1, 45), (3, 0), (3, 15), (3, 30), (3, 45)],
+            "{policy:?}"
+        );
+    }
+}
+
+#[test]
+fn test_gap_policy_iteration() {
+    let start = Pacific.with_ymd_and_hms(2024, 3, 9, 0, 0, 0).unwrap();
+    let end = Pacific.with_ymd_and_hms(2024, 3, 12, 0, 0, 0).unwrap();
+    let days = |policy| {
+        "45 2 * * *"
+            .parse::<Schedule>()
+            .unwrap()
+            .with_gap_policy(policy)
+            .occurrences_between(start..end)
+            .unwrap()
+            .map(|dt| (dt.day(), dt.hour(), dt.minute()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(days(GapPolicy::Skip), vec![(9, 2, 45), (11, 2, 45)]);
+    assert_eq!(
+        days(GapPolicy::FirstValid),
+        vec![(9, 2, 45), (10, 3, 0), (11, 2, 45)]
+    );
+    assert_eq!(
+        days(GapPolicy::Shift),
+        vec![(9, 2, 45), (10, 3, 45), (11, 2, 45)]
+    );
+}
+
+#[test]
+fn test_gap_policy_shift_several_times() {
+    // 2024-03-10 02:00 - 02:59 doesn't exist in Pacific time, 2024-03-31
+    // 01:00 - 01:59 in London
+    let cases = [
+        (
+            "0,30 2 * * *",
+            Pacific.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap(),
+            vec![(10, 3, 0), (10, 3, 30), (11, 2, 0), (11, 2, 30)],
+        ),
+        (
+            "0,30 1 * * 0",
+            London.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap(),
+            vec![(31, 2, 0), (31, 2, 30)],
+        ),
+    ];
+    for (cron, start, expected) in cases {
+        let schedule = cron
+            .parse::<Schedule>()
+            .unwrap()
+            .with_gap_policy(GapPolicy::Shift);
+        let end = start + chrono::Duration::days(2);
+        let forward: Vec<_> = schedule.occurrences_between(start..end).unwrap().collect();
+        let days: Vec<_> = forward
+            .iter()
+            .map(|dt| (dt.day(), dt.hour(), dt.minute()))
+            .collect();
+        assert_eq!(days, expected, "{cron}");
+
+        let mut backward = Vec::new();
+        let mut prev = end;
+        while let Some(dt) = schedule.last_in(start..prev).unwrap() {
+            prev = dt;
+            backward.push(dt);
+        }
+        backward.reverse();
+        assert_eq!(forward, backward, "{cron}");
+    }
+}
+
+#[test]
+fn test_gap_policy_half_hour_shift() {
+    // 2024-10-06 02:00 - 02:29 doesn't exist on Lord Howe Island
+    let before = Lord_Howe.with_ymd_and_hms(2024, 10, 6, 1, 0, 0).unwrap();
+    let schedule = "15 2 * * *"
+        .parse::<Schedule>()
+        .unwrap()
+        .with_gap_policy(GapPolicy::Shift);
+    assert_eq!(
+        schedule.next_after(&before).unwrap(),
+        Lord_Howe.with_ymd_and_hms(2024, 10, 6, 2, 45, 0).unwrap()
+    );
+    let schedule = schedule.with_gap_policy(GapPolicy::FirstValid);
+    assert_eq!(
+        schedule.next_after(&before).unwrap(),
+        Lord_Howe.with_ymd_and_hms(2024, 10, 6, 2, 30, 0).unwrap()
+    );
+}
+
+// offsets in seconds east of UTC of the dates of `cron` between `start` and
+// `end`, with the local hour and minute
+fn overlap_dates<TZ: TimeZone>(
+    cron: &str,
+    policy: OverlapPolicy,
+    start: &chrono::DateTime<TZ>,
+    end: &chrono::DateTime<TZ>,
+) -> Vec<(u32, u32, i32)> {
+    use chrono::Offset;
+    cron.parse::<Schedule>()
+        .unwrap()
+        .with_overlap_policy(policy)
+        .occurrences_between(start.clone()..end.clone())
+        .unwrap()
+        .map(|dt| (dt.hour(), dt.minute(), dt.offset().fix().local_minus_utc()))
+        .collect()
+}
+
+#[test]
+fn test_overlap_policy_pacific() {
+    // 2024-11-03 01:00 - 01:59 happens twice, PDT (-7) then PST (-8)
+    let start = Pacific.with_ymd_and_hms(2024, 11, 3, 0, 0, 0).unwrap();
+    let end = Pacific.with_ymd_and_hms(2024, 11, 3, 3, 0, 0).unwrap();
+    let (pdt, pst) = (-7 * 3600, -8 * 3600);
+
+    assert_eq!(
+        overlap_dates("*/30 1 * * *", OverlapPolicy::Earlier, &start, &end),
+        vec![(1, 0, pdt), (1, 30, pdt)]
+    );
+    assert_eq!(
+        overlap_dates("*/30 1 * * *", OverlapPolicy::Later, &start, &end),
+        vec![(1, 0, pst), (1, 30, pst)]
+    );
+    assert_eq!(
+        overlap_dates("*/30 1 * * *", OverlapPolicy::Both, &start, &end),
+        vec![(1, 0, pdt), (1, 30, pdt), (1, 0, pst), (1, 30, pst)]
+    );
+    assert_eq!(
+        overlap_dates("*/30 * * * *", OverlapPolicy::Both, &start, &end),
+        vec![
+            (0, 0, pdt),
+            (0, 30, pdt),
+            (1, 0, pdt),
+            (1, 30, pdt),
+            (1, 0, pst),
+            (1, 30, pst),
+            (2, 0, pst),
+            (2, 30, pst),
+        ]
+    );
+}
+
+#[test]
+fn test_overlap_policy_count() {
+    let start = Pacific.with_ymd_and_hms(2024, 11, 2, 0, 0, 0).unwrap();
+    let end = Pacific.with_ymd_and_hms(2024, 11, 5, 0, 0, 0).unwrap();
+    for (policy, expected) in [
+        (OverlapPolicy::Earlier, 3 * 24 * 4),
+        (OverlapPolicy::Later, 3 * 24 * 4),
+        (OverlapPolicy::Both, 3 * 24 * 4 + 4),
+    ] {
+        let schedule = "*/15 * * * *"
+            .parse::<Schedule>()
+            .unwrap()
+            .with_overlap_policy(policy);
+        assert_eq!(schedule.count_between(start..end).unwrap(), expected);
+        assert_eq!(
+            schedule.occurrences_between(start..end).unwrap().count() as u64,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_overlap_policy_from_repeated_hour() {
+    let chrono::LocalResult::Ambiguous(first, second) =
+        Pacific.with_ymd_and_hms(2024, 11, 3, 1, 50, 0)
+    else {
+        panic!("01:50 happens twice");
+    };
+    let schedule: Schedule = "*/15 * * * *".parse().unwrap();
+
+    // never returns a date before the start, even in the second hour
+    let next = schedule.next_after(&second).unwrap();
+    assert_eq!(
+        next,
+        Pacific.with_ymd_and_hms(2024, 11, 3, 2, 0, 0).unwrap()
+    );
+    assert!(next > second);
+
+    // from the first hour, the repeated hour comes next
+    let both = schedule.clone().with_overlap_policy(OverlapPolicy::Both);
+    let next = both.next_after(&first).unwrap();
+    assert_eq!((next.hour(), next.minute()), (1, 0));
+    assert!(next > first);
+    assert!(next < second);
+
+    let later = schedule.with_overlap_policy(OverlapPolicy::Later);
+    assert_eq!(later.next_after(&first).unwrap(), next);
+    assert_eq!(later.next_at_or_after(&first).unwrap(), next);
+}
+
+#[test]
+fn test_overlap_policy_london() {
+    // 2024-10-27 01:00 - 01:59 happens twice, BST (+1) then GMT (0)
+    let start = London.with_ymd_and_hms(2024, 10, 27, 0, 0, 0).unwrap();
+    let end = London.with_ymd_and_hms(2024, 10, 27, 2, 0, 0).unwrap();
+    assert_eq!(
+        overlap_dates("30 1 * * *", OverlapPolicy::Both, &start, &end),
+        vec![(1, 30, 3600), (1, 30, 0)]
+    );
+    assert_eq!(
+        overlap_dates("30 1 * * *", OverlapPolicy::Later, &start, &end),
+        vec![(1, 30, 0)]
+    );
+}
+
+#[test]
+fn test_overlap_policy_half_hour() {
+    // 2024-04-07 01:30 - 01:59 happens twice on Lord Howe Island
+    let start = Lord_Howe.with_ymd_and_hms(2024, 4, 7, 1, 0, 0).unwrap();
+    let end = Lord_Howe.with_ymd_and_hms(2024, 4, 7, 2, 30, 0).unwrap();
+    let (summer, winter) = (11 * 3600, 10 * 3600 + 1800);
+    assert_eq!(
+        overlap_dates("*/20 * * * *", OverlapPolicy::Both, &start, &end),
+        vec![
+            (1, 0, summer),
+            (1, 20, summer),
+            (1, 40, summer),
+            (1, 40, winter),
+            (2, 0, winter),
+            (2, 20, winter),
+        ]
+    );
+}
+
+fn vixie_dates<TZ: TimeZone>(
+    cron: &str,
+    start: &chrono::DateTime<TZ>,
+    end: &chrono::DateTime<TZ>,
+) -> Vec<(u32, u32, i32)> {
+    use chrono::Offset;
+    cron.parse::<Schedule>()
+        .unwrap()
+        .with_vixie_dst()
+        .occurrences_between(start.clone()..end.clone())
+        .unwrap()
+        .map(|dt| (dt.hour(), dt.minute(), dt.offset().fix().local_minus_utc()))
+        .collect()
+}
+
+#[test]
+fn test_vixie_dst_forward() {
+    // 2024-03-10 02:00 - 02:59 doesn't exist in Pacific time
+    let start = Pacific.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+    let end = Pacific.with_ymd_and_hms(2024, 3, 10, 4, 0, 0).unwrap();
+    let (pst, pdt) = (-8 * 3600, -7 * 3600);
+
+    // fixed-time jobs run right after the gap
+    assert_eq!(vixie_dates("30 2 * * *", &start, &end), vec![(3, 0, pdt)]);
+    assert_eq!(
+        vixie_dates("15 1-3 * * *", &start, &end),
+        vec![(1, 15, pst), (3, 0, pdt), (3, 15, pdt)]
+    );
+    // wildcard jobs skip it
+    assert_eq!(vixie_dates("*/30 2 * * *", &start, &end), vec![]);
+    assert_eq!(
+        vixie_dates("0 * * * *", &start, &end),
+        vec![(0, 0, pst), (1, 0, pst), (3, 0, pdt)]
+    );
+}
+
+#[test]
+fn test_vixie_dst_back() {
+    // 2024-11-03 01:00 - 01:59 happens twice in Pacific time
+    let start = Pacific.with_ymd_and_hms(2024, 11, 3, 0, 30, 0).unwrap();
+    let end = Pacific.with_ymd_and_hms(2024, 11, 3, 3, 0, 0).unwrap();
+    let (pdt, pst) = (-7 * 3600, -8 * 3600);
+
+    // fixed-time jobs run once
+    assert_eq!(vixie_dates("30 1 * * *", &start, &end), vec![(1, 30, pdt)]);
+    // wildcard jobs run in both hours
+    assert_eq!(
+        vixie_dates("*/30 1 * * *", &start, &end),
+        vec![(1, 0, pdt), (1, 30, pdt), (1, 0, pst), (1, 30, pst)]
+    );
+    assert_eq!(
+        vixie_dates("0 * * * *", &start, &end),
+        vec![(1, 0, pdt), (1, 0, pst), (2, 0, pst)]
+    );
+}
+
+#[test]
+fn test_vixie_dst_long_shift() {
+    // Samoa skipped 2011-12-30 moving across the date line, that's not DST
+    let start = Apia.with_ymd_and_hms(2011, 12, 29, 13, 0, 0).unwrap();
+    let schedule = "0 12 * * *".parse::<Schedule>().unwrap();
+    let expected = Apia.with_ymd_and_hms(2011, 12, 31, 12, 0, 0).unwrap();
+    assert_eq!(
+        schedule
+            .clone()
+            .with_vixie_dst()
+            .next_after(&start)
+            .unwrap(),
+        expected
+    );
+    let first_valid = schedule.with_gap_policy(GapPolicy::FirstValid);
+    assert_eq!(
+        first_valid.next_after(&start).unwrap(),
+        Apia.with_ymd_and_hms(2011, 12, 31, 0, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_vixie_dst_overrides_policies() {
+    let before = Pacific.with_ymd_and_hms(2024, 3, 10, 1, 0, 0).unwrap();
+    let schedule = "30 2 * * *"
+        .parse::<Schedule>()
+        .unwrap()
+        .with_gap_policy(GapPolicy::Shift)
+        .with_vixie_dst();
+    assert_eq!(
+        schedule.next_after(&before).unwrap(),
+        Pacific.with_ymd_and_hms(2024, 3, 10, 3, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_prev_reverses_next_across_dst() {
+    let crons = [
+        "*/15 * * * *",
+        "30 1,2 * * *",
+        "0 * * * *",
+        "*/20 1-3 * * *",
+    ];
+    let windows = [
+        (
+            Pacific.with_ymd_and_hms(2024, 3, 9, 22, 0, 0).unwrap(),
+            Pacific.with_ymd_and_hms(2024, 3, 10, 5, 0, 0).unwrap(),
+        ),
+        (
+            Pacific.with_ymd_and_hms(2024, 11, 2, 22, 0, 0).unwrap(),
+            Pacific.with_ymd_and_hms(2024, 11, 3, 5, 0, 0).unwrap(),
+        ),
+    ];
+    let configs: [fn(Schedule) -> Schedule; 6] = [
+        |schedule| schedule,
+        |schedule| schedule.with_gap_policy(GapPolicy::FirstValid),
+        |schedule| schedule.with_overlap_policy(OverlapPolicy::Later),
+        |schedule| schedule.with_overlap_policy(OverlapPolicy::Both),
+        |schedule| {
+            schedule
+                .with_gap_policy(GapPolicy::FirstValid)
+                .with_overlap_policy(OverlapPolicy::Both)
+        },
+        Schedule::with_vixie_dst,
+    ];
+    for cron in crons {
+        for config in configs {
+            let schedule = config(cron.parse().unwrap());
+            for (start, end) in &windows {
+                let forward: Vec<_> = schedule.occurrences_between(start..end).unwrap().collect();
+                let mut backward = Vec::new();
+                let mut prev = schedule.prev_before(end).unwrap();
+                while prev >= *start {
+                    backward.push(prev);
+                    prev = schedule.prev_before(&prev).unwrap();
+                }
+                backward.reverse();
+                assert_eq!(forward, backward, "{cron} {schedule:?} from {start}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_prev_at_or_before() {
+    let schedule: Schedule = "0 12 * * *".parse().unwrap();
+    let noon = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    assert_eq!(schedule.prev_at_or_before(&noon).unwrap(), noon);
+    assert_eq!(
+        schedule.prev_before(&noon).unwrap(),
+        Utc.with_ymd_and_hms(2023, 12, 31, 12, 0, 0).unwrap()
+    );
+    let after = noon + chrono::Duration::seconds(30);
+    assert_eq!(schedule.prev_before(&after).unwrap(), noon);
+    assert_eq!(schedule.prev_at_or_before(&after).unwrap(), noon);
+}
+
+#[test]
+fn test_prev_horizon() {
+    // Feb 29 on a Sunday, 2032 then 2004
+    let schedule: Schedule = "0 0 29 2 0".parse().unwrap();
+    let now = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    assert!(matches!(
+        schedule.prev_before(&now),
+        Err(ParseError::HorizonExceeded)
+    ));
+    assert_eq!(
+        schedule.without_horizon().prev_before(&now).unwrap(),
+        Utc.with_ymd_and_hms(2004, 2, 29, 0, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_limits_of_chrono() {
+    let max = chrono::DateTime::<Utc>::MAX_UTC;
+    let min = chrono::DateTime::<Utc>::MIN_UTC;
+    let every_minute: Schedule = "* * * * *".parse().unwrap();
+    let new_year: Schedule = "0 0 1 1 *".parse().unwrap();
+    let midnight: Schedule = "0 0 * * *".parse().unwrap();
+
+    // no date after the last one chrono can represent, or before the first
+    for schedule in [every_minute.clone(), every_minute.clone().without_horizon()] {
+        assert!(matches!(
+            schedule.next_after(&max),
+            Err(ParseError::HorizonExceeded)
+        ));
+        assert!(matches!(
+            schedule.prev_before(&min),
+            Err(ParseError::HorizonExceeded)
+        ));
+        assert_eq!(
+            schedule
+                .first_in((Bound::Excluded(max), Bound::Unbounded))
+                .unwrap(),
+            None
+        );
+    }
+    let last_year = Utc.with_ymd_and_hms(262_142, 6, 1, 0, 0, 0).unwrap();
+    assert!(matches!(
+        new_year.next_after(&last_year),
+        Err(ParseError::HorizonExceeded)
+    ));
+    let first_year = Utc.with_ymd_and_hms(-262_143, 6, 1, 0, 0, 0).unwrap();
+    assert!(matches!(
+        "59 23 31 12 *"
+            .parse::<Schedule>()
+            .unwrap()
+            .prev_before(&first_year),
+        Err(ParseError::HorizonExceeded)
+    ));
+
+    // the dates up to the limits are found
+    assert_eq!(
+        new_year.prev_at_or_before(&max).unwrap(),
+        Utc.with_ymd_and_hms(262_142, 1, 1, 0, 0, 0).unwrap()
+    );
+    assert_eq!(every_minute.next_at_or_after(&min).unwrap(), min);
+    let start = Utc.with_ymd_and_hms(262_142, 12, 31, 23, 30, 0).unwrap();
+    assert_eq!(midnight.occurrences_between(start..).unwrap().count(), 0);
+    let start = Utc.with_ymd_and_hms(262_142, 12, 30, 0, 0, 0).unwrap();
+    assert_eq!(midnight.occurrences_between(start..).unwrap().count(), 2);
+    assert_eq!(every_minute.count_between(start..max).unwrap(), 2 * 24 * 60);
+}
+
+#[test]
+fn test_recurrence_range() {
+    let schedule: Schedule = "0 */6 * * *".parse().unwrap();
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+
+    assert_eq!(schedule.first_in(start..end).unwrap(), Some(start));
+    assert_eq!(
+        schedule
+            .first_in((Bound::Excluded(start), Bound::Included(end)))
+            .unwrap(),
+        Some(Utc.with_ymd_and_hms(2024, 1, 1, 6, 0, 0).unwrap())
+    );
+    assert_eq!(
+        schedule.last_in(start..end).unwrap(),
+        Some(Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap())
+    );
+    assert_eq!(schedule.last_in(start..=end).unwrap(), Some(end));
+    let short = start + chrono::Duration::hours(1)..start + chrono::Duration::hours(5);
+    assert_eq!(schedule.first_in(short.clone()).unwrap(), None);
+    assert_eq!(schedule.last_in(short).unwrap(), None);
+    assert!(matches!(
+        schedule.first_in(..end),
+        Err(ParseError::InvalidRange)
+    ));
+    assert!(matches!(
+        schedule.last_in(start..),
+        Err(ParseError::InvalidRange)
+    ));
+}
+
+#[test]
+fn test_interval() {
+    let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let interval = Interval::new(&anchor, chrono::Duration::minutes(90)).unwrap();
+    assert_eq!(interval.anchor(), anchor);
+    assert_eq!(interval.period(), chrono::Duration::minutes(90));
+
+    let end = Utc.with_ymd_and_hms(2024, 1, 1, 6, 0, 0).unwrap();
+    let dates: Vec<_> = interval
+        .occurrences_between(anchor..end)
+        .unwrap()
+        .map(|dt| (dt.hour(), dt.minute()))
+        .collect();
+    assert_eq!(dates, vec![(0, 0), (1, 30), (3, 0), (4, 30)]);
+
+    assert!(interval.matches(&end));
+    assert!(!interval.matches(&(end + chrono::Duration::seconds(1))));
+    assert_eq!(interval.next_at_or_after(&end).unwrap(), end);
+    assert_eq!(
+        interval.next_after(&end).unwrap(),
+        end + chrono::Duration::minutes(90)
+    );
+    assert_eq!(interval.prev_at_or_before(&end).unwrap(), end);
+    assert_eq!(
+        interval.prev_before(&end).unwrap(),
+        end - chrono::Duration::minutes(90)
+    );
+
+    // the anchor only aligns the dates
+    assert_eq!(
+        interval.prev_before(&anchor).unwrap(),
+        Utc.with_ymd_and_hms(2023, 12, 31, 22, 30, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_interval_sub_second() {
+    let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let interval = Interval::new(&anchor, chrono::Duration::milliseconds(1500)).unwrap();
+    let now = anchor + chrono::Duration::seconds(2);
+    assert_eq!(
+        interval.next_after(&now).unwrap(),
+        anchor + chrono::Duration::seconds(3)
+    );
+    assert_eq!(
+        interval.prev_before(&now).unwrap(),
+        anchor + chrono::Duration::milliseconds(1500)
+    );
+}
+
+#[test]
+fn test_interval_invalid_period() {
+    let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    for period in [chrono::Duration::zero(), chrono::Duration::minutes(-90)] {
+        assert!(matches!(
+            Interval::new(&anchor, period),
+            Err(ParseError::InvalidRange)
+        ));
+    }
+}
+
+#[test]
+fn test_interval_ignores_dst() {
+    // 36 hours apart in elapsed time, 2019-11-03 01:00 happens twice
+    let anchor = Chicago.with_ymd_and_hms(2019, 11, 2, 12, 0, 0).unwrap();
+    let interval = Interval::new(&anchor, chrono::Duration::hours(36)).unwrap();
+    let next = interval.next_after(&anchor).unwrap();
+    assert_eq!(
+        next,
+        Chicago.with_ymd_and_hms(2019, 11, 3, 23, 0, 0).unwrap()
+    );
+    assert_eq!(next.timezone(), Chicago);
+}
+
+#[test]
+fn test_recurrence_generic() {
+    fn runs<R: Recurrence>(recurrence: &R) -> usize {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        recurrence.occurrences_between(start..end).unwrap().count()
+    }
+
+    let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let schedule: Schedule = "0 */6 * * *".parse().unwrap();
+    assert_eq!(runs(&schedule), 4);
+    assert_eq!(
+        runs(&Interval::new(&anchor, chrono::Duration::hours(36)).unwrap()),
+        1
+    );
+}
+
+#[test]
+fn test_display_canonical() {
+    let cases = [
+        ("* * * * *", "* * * * *"),
+        ("0,15,30,45 * * * *", "*/15 * * * *"),
+        ("*/15 * * * *", "*/15 * * * *"),
+        ("0-59/15 * * * *", "*/15 * * * *"),
+        ("0-59 0-23 1-31 1-12 0-6", "0-59 0-23 * * *"),
+        ("5,20,35,50 * * * *", "5-50/15 * * * *"),
+        ("0 1/6 * * *", "0 1-19/6 * * *"),
+        ("0 0,12 * * *", "0 0,12 * * *"),
+        ("30 9 * * Mon-Fri", "30 9 * * 1-5"),
+        ("30 9 * * Mon,Tue,Wed,Thu,Fri", "30 9 * * 1-5"),
+        ("0 0 * * Sun,Sat", "0 0 * * 0,6"),
+        ("0 0 1 1,4,7,10 *", "0 0 1 */3 *"),
+        ("0 0 1 6,7,8 *", "0 0 1 6-8 *"),
+        ("2-3,9,*/15,1-8,11,9,4,5 * * * *", "*/15,1-9,11 * * * *"),
+        ("0-10,20,30,40 * * * *", "0-40/10,1-9 * * * *"),
+        ("0,1,2,10,20,30 * * * *", "0-30/10,1,2 * * * *"),
+        ("30,20,10,2,1,0 * * * *", "0-30/10,1,2 * * * *"),
+        // terms are disjoint, steps only when shorter than the values
+        ("0-20/2,5,15 * * * *", "0-20/2,5,15 * * * *"),
+        ("1-3,5,9 * * * *", "1-3,5,9 * * * *"),
+        ("1,3,5 * * * *", "1,3,5 * * * *"),
+        ("0 0 * * 1-5/2", "0 0 * * 1,3,5"),
+        ("59 23 31 12 *", "59 23 31 12 *"),
+        ("0 0 29 2 *", "0 0 29 2 *"),
+    ];
+    for (cron, expected) in cases {
+        let schedule: Schedule = cron.parse().unwrap();
+        assert_eq!(schedule.to_string(), expected, "{cron}");
+    }
+}
+
+#[test]
+fn test_display_vixie_wildcard() {
+    let cases = [
+        ("0,15,30,45 3 * * *", "0-45/15 3 * * *"),
+        ("*/15 3 * * *", "*/15 3 * * *"),
+        ("0-59 0-23 * * *", "0-59 0-23 * * *"),
+        ("* 0-23 * * *", "* * * * *"),
+        ("0 */6 * * *", "0 */6 * * *"),
+        ("0 0,6,12,18 * * *", "0 0-18/6 * * *"),
+        ("*/20,1,2 5 * * *", "*/20,1,2 5 * * *"),
+        ("0,1,2,20,40 */12 * * *", "*/20,1,2 0,12 * * *"),
+        // `*/15` is written `0-45/15` without the leading star
+        ("0,1,15,30,45 3 * * *", "0-45/15,1 3 * * *"),
+    ];
+    for (cron, expected) in cases {
+        let schedule: Schedule = cron.parse().unwrap();
+        let display = schedule.to_string();
+        assert_eq!(display, expected, "{cron}");
+        let reparsed: Schedule = display.parse().unwrap();
+        assert_eq!(
+            reparsed.with_vixie_dst(),
+            schedule.with_vixie_dst(),
+            "{cron}"
+        );
+    }
+}
+
+#[test]
+fn test_from_english_vixie_wildcard() {
+    let cases = [
+        ("every 15 minutes", "*/15 * * * *"),
+        ("every 15 minutes between 9am and 5pm", "*/15 9-16 * * *"),
+        ("every hour", "0 * * * *"),
+        ("hourly", "0 * * * *"),
+        ("every 2 hours", "0 */2 * * *"),
+        ("every hour between 9am and 5pm", "0 9-16 * * *"),
+        ("every 2 hours between 9am and 5pm", "0 9-15/2 * * *"),
+        ("at 3am and 3pm", "0 3,15 * * *"),
+    ];
+    for (phrase, cron) in cases {
+        let schedule = Schedule::from_english(phrase).unwrap().with_vixie_dst();
+        let parsed: Schedule = cron.parse().unwrap();
+        assert_eq!(schedule, parsed.with_vixie_dst(), "{phrase}");
+        assert_eq!(schedule.to_string(), cron, "{phrase}");
+    }
+}
+
+#[test]
+fn test_display_round_trip() {
+    let crons = [
+        "*/7 * * * *",
+        "1,2,4,8,16,32 * * * *",
+        "*/30,40-45,57,30,44,41-45 1-3,5,7-23/4 * * *",
+        "0 0 1-31/5 * *",
+        "15,45 9-17 * 2-11/3 1-5",
+        "3,4,5,7,9,11,13,20,21 0 * * 0,2,3,4",
+        "1,4,6,7,8,13,17,22,23,29,31,36,40,41,47,50,55,58 */5 * * *",
+    ];
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    for cron in crons {
+        let schedule: Schedule = cron.parse().unwrap();
+        let display = schedule.to_string();
+        let reparsed: Schedule = display.parse().unwrap();
+        assert_eq!(reparsed.to_string(), display, "{cron}");
+        assert!(
+            schedule
+                .occurrences_between(start..end)
+                .unwrap()
+                .eq(reparsed.occurrences_between(start..end).unwrap()),
+            "{cron} -> {display}"
+        );
+    }
+}
+
+#[test]
+fn test_describe() {
+    let cases = [
+        ("* * * * *", "Every minute"),
+        ("*/15 * * * *", "Every 15 minutes"),
+        ("0,15,30,45 * * * *", "Every 15 minutes"),
+        (
+            "0,7,15,30,45 * * * *",
+            "At every 15th minute and minute 7 past the hour",
+        ),
+        ("30 9 * * 1-5", "At 09:30, Monday through Friday"),
+        (
+            "30 9 * * Mon,Tue,Wed,Thu,Fri",
+            "At 09:30, Monday through Friday",
+        ),
+        ("0 0 * * 0", "At 00:00, only on Sunday"),
+        ("0 0 * * Sat,Sun", "At 00:00, only on Sunday and Saturday"),
+        ("0 9,12,17 * * *", "At 09:00, 12:00 and 17:00"),
+        (
+            "*/15 12-18 * 3 *",
+            "Every 15 minutes between 12:00 and 18:59, only in March",
+        ),
+        ("* 9 * * *", "Every minute between 09:00 and 09:59"),
+        ("5 * * * *", "At minute 5 past the hour"),
+        ("0,30 * * * *", "At minutes 0 and 30 past the hour"),
+        ("0 */6 * * *", "At minute 0 past every 6th hour"),
+        (
+            "0 1/6 * * *",
+            "At minute 0 past every 6th hour from 1 through 19",
+        ),
+        (
+            "0,30 9-17 * * *",
+            "At minutes 0 and 30 between 09:00 and 17:59",
+        ),
+        (
+            "10-20/5 1-3,5,9 * * *",
+            "At every 5th minute from 10 through 20 past hours 1 through 3, 5 and 9",
+        ),
+        ("* 9,17 * * *", "Every minute during hours 9 and 17"),
+        ("0 0 1 * *", "At 00:00, on day 1 of the month"),
+        ("0 0 1,15 * *", "At 00:00, on days 1 and 15 of the month"),
+        ("0 0 */5 * *", "At 00:00, on every 5th day of the month"),
+        (
+            "0 0 13 * 5",
+            "At 00:00, on day 13 of the month, if it falls on Friday",
+        ),
+        (
+            "0 0 1-7 * Mon",
+            "At 00:00, on days 1 through 7 of the month, if it falls on Monday",
+        ),
+        (
+            "0 0 1 1,4,7,10 *",
+            "At 00:00, on day 1 of the month, only in every 3rd month",
+        ),
+        (
+            "0 0 * 6-8 */2",
+            "At 00:00, only on every 2nd day of the week, only in June through August",
+        ),
+        (
+            "0 12 * 2,3,5 *",
+            "At 12:00, only in February, March and May",
+        ),
+        ("0 0 * */11 *", "At 00:00, only in January and December"),
+        (
+            "59 23 31 12 *",
+            "At 23:59, on day 31 of the month, only in December",
+        ),
+    ];
+    for (cron, expected) in cases {
+        let schedule: Schedule = cron.parse().unwrap();
+        assert_eq!(schedule.describe().to_string(), expected, "{cron}");
+    }
+}
+
+#[test]
+fn test_describe_ordinals() {
+    struct Ordinal(u32);
+    impl std::fmt::Display for Ordinal {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            (Locale::ENGLISH.ordinal)(self.0, f)
+        }
+    }
+
+    // larger steps match 3 values, written as a list
+    for (step, ordinal) in [
+        (2, "2nd"),
+        (3, "3rd"),
+        (4, "4th"),
+        (11, "11th"),
+        (12, "12th"),
+    ] {
+        let cron = format!("1/{step} * * * *");
+        let schedule: Schedule = cron.parse().unwrap();
+        let last = 1 + (58 / step) * step;
+        assert_eq!(
+            schedule.describe().to_string(),
+            format!("At every {ordinal} minute from 1 through {last} past the hour"),
+            "{cron}"
+        );
+    }
+    for (number, ordinal) in [
+        (1, "1st"),
+        (13, "13th"),
+        (21, "21st"),
+        (22, "22nd"),
+        (23, "23rd"),
+    ] {
+        assert_eq!(Ordinal(number).to_string(), ordinal);
+    }
+}
+
+#[test]
+fn test_describe_in() {
+    let crons = [
+        "30 9 * * 1-5",
+        "0 0 * * Sat,Sun",
+        "0 9,12,17 * * *",
+        "0,30 * * * *",
+        "0 */6 * * *",
+        "0 0 1,15 * *",
+        "0 0 13 * 5",
+        "0 0 1 */3 *",
+        "10-20/5 * * * *",
+        "0,1,2,10,20,30 * * * *",
+        "0 0 */2 * *",
+        "0 9 * * */2",
+        "0 0 1 2-11/3 *",
+    ];
+    let expected = [
+        (
+            Locale::SPANISH,
+            [
+                "A las 09:30, de lunes a viernes",
+                "A las 00:00, solo en domingo y sábado",
+                "A las 09:00, 12:00 y 17:00",
+                "En minutos 0 y 30 de cada hora",
+                "En minuto 0 de cada 6.ª hora",
+                "A las 00:00, días 1 y 15 del mes",
+                "A las 00:00, día 13 del mes, si cae en viernes",
+                "A las 00:00, día 1 del mes, solo en cada 3.º mes",
+                "En cada 5.º minuto de 10 a 20 de cada hora",
+                "En cada 10.º minuto de 0 a 30, minutos 1 y 2 de cada hora",
+                "A las 00:00, cada 2.º día del mes",
+                "A las 09:00, solo en cada 2.º día de la semana",
+                "A las 00:00, día 1 del mes, solo en cada 3.º mes de febrero a noviembre",
+            ],
+        ),
+        (
+            Locale::GERMAN,
+            [
+                "Um 09:30, Montag bis Freitag",
+                "Um 00:00, nur an Sonntag und Samstag",
+                "Um 09:00, 12:00 und 17:00",
+                "In Minuten 0 und 30 in jeder Stunde",
+                "In Minute 0 in jeder 6. Stunde",
+                "Um 00:00, an Tagen 1 und 15 des Monats",
+                "Um 00:00, an Tag 13 des Monats, nur wenn es Freitag ist",
+                "Um 00:00, an Tag 1 des Monats, nur im Abstand von 3 Monaten",
+                "In jeder 5. Minute von 10 bis 20 in jeder Stunde",
+                "In jeder 10. Minute von 0 bis 30, Minuten 1 und 2 in jeder Stunde",
+                "Um 00:00, an jedem 2. Tag des Monats",
+                "Um 09:00, nur an jedem 2. Wochentag",
+                "Um 00:00, an Tag 1 des Monats, nur im Abstand von 3 Monaten von Februar bis November",
+            ],
+        ),
+        (
+            Locale::PORTUGUESE,
+            [
+                "Às 09:30, de segunda-feira a sexta-feira",
+                "Às 00:00, somente domingo e sábado",
+                "Às 09:00, 12:00 e 17:00",
+                "Em minutos 0 e 30 de cada hora",
+                "Em minuto 0 de cada 6ª hora",
+                "Às 00:00, dias 1 e 15 do mês",
+                "Às 00:00, dia 13 do mês, se cair em sexta-feira",
+                "Às 00:00, dia 1 do mês, somente em cada 3º mês",
+                "Em cada 5º minuto de 10 a 20 de cada hora",
+                "Em cada 10º minuto de 0 a 30, minutos 1 e 2 de cada hora",
+                "Às 00:00, cada 2º dia do mês",
+                "Às 09:00, somente cada 2º dia da semana",
+                "Às 00:00, dia 1 do mês, somente em cada 3º mês de fevereiro a novembro",
+            ],
+        ),
+    ];
+    for (locale, descriptions) in expected {
+        for (cron, description) in crons.iter().zip(descriptions) {
+            let schedule: Schedule = cron.parse().unwrap();
+            assert_eq!(
+                schedule.describe_in(&locale).to_string(),
+                description,
+                "{cron}"
+            );
+        }
+    }
+
+    let schedule: Schedule = "*/5 * * * *".parse().unwrap();
+    assert_eq!(
+        schedule.describe_in(&Locale::ENGLISH).to_string(),
+        schedule.describe().to_string()
+    );
+}
+
+#[test]
+fn test_describe_in_custom_locale() {
+    let mut locale = Locale::ENGLISH;
+    locale.weekdays = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    locale.weekday_range = " ({0})";
+    locale.range = "{0}-{1}";
+    locale.at_times = "{0} {unchanged} {9}";
+    locale.ordinal = |number, f| write!(f, "#{number}");
+    locale.hour.every_nth = "each {0} hour";
+
+    let schedule: Schedule = "30 9 * * Mon-Fri".parse().unwrap();
+    assert_eq!(
+        schedule.describe_in(&locale).to_string(),
+        "09:30 {unchanged} {9} (Mon-Fri)"
+    );
+    let schedule: Schedule = "* */2 * * *".parse().unwrap();
+    assert_eq!(
+        schedule.describe_in(&locale).to_string(),
+        "Every minute during each #2 hour"
+    );
+}
+
+#[test]
+fn test_from_english() {
+    let cases = [
+        ("every minute", "* * * * *"),
+        ("every 15 minutes between 9am and 5pm", "*/15 9-16 * * *"),
+        ("every weekday at 9am", "0 9 * * 1-5"),
+        ("Every 2 hours on weekends", "0 */2 * * 0,6"),
+        ("every hour from 10pm to 2am", "0 0,1,22,23 * * *"),
+        ("daily at 9:30 pm", "30 21 * * *"),
+        ("at noon and midnight", "0 0,12 * * *"),
+        (
+            "at 8:15, 12:15 and 17:15 on mon, wed and fri",
+            "15 8,12,17 * * 1,3,5",
+        ),
+        ("on the 1st and 15th of the month at 6am", "0 6 1,15 * *"),
+        ("every monday in june through august", "0 0 * 6-8 1"),
+        ("from friday to monday at 23:45", "45 23 * * 0,1,5,6"),
+        ("every 3 months", "0 0 1 */3 *"),
+        ("weekly", "0 0 * * 0"),
+        ("yearly", "0 0 1 1 *"),
+        ("every month on the 15th", "0 0 15 * *"),
+        ("monthly on the 15th", "0 0 15 * *"),
+        ("on the 15th monthly", "0 0 15 * *"),
+        ("every 3 months on the 1st at 9am", "0 9 1 */3 *"),
+        ("yearly in june", "0 0 1 6 *"),
+        ("weekly on monday", "0 0 * * 1"),
+        ("every 2 hours between 9am and 5pm", "0 9-15/2 * * *"),
+        ("between 10pm and 4am every 2 hours", "0 0,2,22 * * *"),
+    ];
+    for (phrase, expected) in cases {
+        let schedule = Schedule::from_english(phrase).unwrap();
+        assert_eq!(schedule.to_string(), expected, "{phrase}");
+    }
+}
+
+#[test]
+fn test_from_english_unsupported() {
+    let cases = [
+        ("", 0),
+        ("every fortnight", 6),
+        ("every 2 weeks", 8),
+        ("every 90 minutes", 6),
+        ("at 9am and 5:30pm", 11),
+        ("at 25:00", 3),
+        ("at 9am at 10am", 7),
+        ("on the 1st of march", 14),
+        ("every day at", 12),
+        ("between 9:30 and 5pm", 8),
+        ("monthly yearly", 8),
+        ("every 2 hours hourly", 14),
+        ("every 2 hours at 9am", 14),
+    ];
+    for (phrase, offset) in cases {
+        assert!(
+            matches!(Schedule::from_english(phrase), Err(ParseError::UnsupportedPhrase(o)) if o == offset),
+            "{phrase}"
+        );
+    }
+    assert!(matches!(
+        Schedule::from_english("on the 31st in february"),
+        Err(ParseError::NeverMatches)
+    ));
+}
+
+#[test]
+fn test_builder() {
+    use chrono::Weekday::{Fri, Mon, Sat, Sun};
+
+    let built = Schedule::builder()
+        .minutes([0, 30])
+        .hours(9..=17)
+        .weekdays(Mon..=Fri)
+        .build()
+        .unwrap();
+    let parsed: Schedule = "0,30 9-17 * * Mon-Fri".parse().unwrap();
+    assert_eq!(built.to_string(), parsed.to_string());
+    let dt = Utc.with_ymd_and_hms(2024, 1, 5, 17, 45, 0).unwrap();
+    assert_eq!(
+        built.next_after(&dt).unwrap(),
+        parsed.next_after(&dt).unwrap()
+    );
+
+    let cases = [
+        (Schedule::builder().build().unwrap(), "* * * * *"),
+        (
+            Schedule::builder()
+                .minutes([15])
+                .weekdays(Fri..=Mon)
+                .build()
+                .unwrap(),
+            "15 * * * 0,1,5,6",
+        ),
+        (
+            Schedule::builder()
+                .minutes([0])
+                .hours([0])
+                .weekdays([Sun, Sat])
+                .build()
+                .unwrap(),
+            "0 0 * * 0,6",
+        ),
+        (
+            Schedule::builder()
+                .minutes((0..60).step_by(15))
+                .days_of_month([1, 15])
+                .months(1..=3)
+                .build()
+                .unwrap(),
+            "*/15 * 1,15 1-3 *",
+        ),
+    ];
+    for (schedule, expected) in cases {
+        assert_eq!(schedule.to_string(), expected);
+    }
+}
+
+#[test]
+fn test_builder_vixie_dst() {
+    let cases = [
+        (
+            Schedule::builder().minutes([0, 15, 30, 45]).hours([3]),
+            "0,15,30,45 3 * * *",
+        ),
+        (Schedule::builder().minutes([0]), "0 * * * *"),
+        (Schedule::builder().hours([2]), "* 2 * * *"),
+        (
+            Schedule::builder().minutes(0..60).hours(0..24),
+            "0-59 0-23 * * *",
+        ),
+    ];
+    for (builder, cron) in cases {
+        let built = builder.build().unwrap().with_vixie_dst();
+        let parsed: Schedule = cron.parse().unwrap();
+        assert_eq!(built, parsed.with_vixie_dst(), "{cron}");
+    }
+    let built = Schedule::builder()
+        .minutes([0])
+        .hours(0..24)
+        .build()
+        .unwrap();
+    let wildcard: Schedule = "0 * * * *".parse().unwrap();
+    assert_ne!(built.with_vixie_dst(), wildcard.with_vixie_dst());
+}
+
+#[test]
+fn test_builder_errors() {
+    match Schedule::builder().minutes([60]).hours(5..5).build() {
+        Err(ParseError::InvalidFields(errors)) => {
+            let fields: Vec<Field> = errors.iter().map(|e| e.field).collect();
+            assert_eq!(fields, vec![Field::Minute, Field::Hour]);
+            assert!(matches!(
+                errors.first().unwrap().error,
+                ParseError::InvalidValue
+            ));
+            assert!(matches!(
+                errors.last().unwrap().error,
+                ParseError::InvalidRange
+            ));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert!(matches!(
+        Schedule::builder().days_of_month([0]).build(),
+        Err(ParseError::InvalidFields(_))
+    ));
+    assert!(matches!(
+        Schedule::builder()
+            .days_of_month([30, 31])
+            .months([2])
+            .build(),
+        Err(ParseError::NeverMatches)
+    ));
+}
+
+#[test]
+fn test_schedule_eq_hash() {
+    use std::collections::HashSet;
+
+    let parse = |cron: &str| cron.parse::<Schedule>().unwrap();
+    let jobs = [
+        parse("0 0 * * 0"),
+        parse("0 0 * * Sun"),
+        parse("0 0 1-31 1-12 0"),
+        parse("0 0 * * 0").with_horizon(10),
+        Schedule::from_english("weekly").unwrap(),
+        parse("0-59/30 * * * *"),
+        parse("0,30 * * * *"),
+        parse("0 0 * * 1"),
+        parse("0 0 * * 0").with_gap_policy(GapPolicy::Shift),
+    ];
+    let unique: HashSet<&Schedule> = jobs.iter().collect();
+    assert_eq!(unique.len(), 4);
+
+    // Vixie cron treats wildcard jobs differently
+    assert_eq!(
+        parse("0 0 * * *"),
+        parse("0 0 * * *").with_overlap_policy(OverlapPolicy::Earlier)
+    );
+    assert_ne!(
+        parse("*/30 * * * *").with_vixie_dst(),
+        parse("0,30 0-23 * * *").with_vixie_dst()
+    );
+    assert_eq!(
+        parse("*/30 * * * *").with_vixie_dst(),
+        parse("0,30 * * * *").with_vixie_dst()
+    );
+    assert_eq!(
+        parse("0 2 * * *").with_vixie_dst(),
+        parse("0 2 * * *")
+            .with_vixie_dst()
+            .with_gap_policy(GapPolicy::Shift)
+    );
+}
+
+#[test]
+fn test_schedule_is_equivalent() {
+    let parse = |cron: &str| cron.parse::<Schedule>().unwrap();
+    let cases = [
+        ("0 0 31 * *", "0 0 31 1,3,5,7,8,10,12 *", true),
+        ("0 0 30,31 2,4 *", "0 0 30 4 *", true),
+        ("0 0 29 2 *", "0 0 29-31 2 *", true),
+        ("0 0 29 2 *", "0 0 29 2,3 *", false),
+        ("0 0 1-7 * 1", "0 0 * * 1", false),
+        ("0 0 1-7 * 1", "0 0 1-7 * Mon", true),
+        ("0 0 31 4,5 Fri", "0 0 31 5 5", true),
+        ("0 0 31 4,5 Fri", "0 0 31 5 6", false),
+    ];
+    for (a, b, expected) in cases {
+        assert_eq!(parse(a).is_equivalent(&parse(b)), expected, "{a} / {b}");
+        assert_eq!(parse(b).is_equivalent(&parse(a)), expected, "{b} / {a}");
+        if parse(a) == parse(b) {
+            assert!(parse(a).is_equivalent(&parse(b)));
+        }
+    }
+    assert!(
+        !parse("0 0 30 4 *")
+            .is_equivalent(&parse("0 0 30 4 *").with_overlap_policy(OverlapPolicy::Both))
+    );
+}
+
+#[test]
+fn test_combinators_brute_force() {
+    let start = Utc.with_ymd_and_hms(2024, 1, 5, 0, 0, 0).unwrap();
+    let end = start + chrono::Duration::days(3);
+    let (a, b) = ("*/15 9-17 * * 1-5", "* 12 * * *");
+    let (in_a, in_b) = (brute_force_matcher(a), brute_force_matcher(b));
+    let (a, b): (Schedule, Schedule) = (a.parse().unwrap(), b.parse().unwrap());
+
+    let mut minutes = Vec::new();
+    let mut dt = start;
+    while dt < end {
+        minutes.push(dt);
+        dt += chrono::Duration::minutes(1);
+    }
+    let expected = |keep: &dyn Fn(&chrono::DateTime<Utc>) -> bool| -> Vec<_> {
+        minutes.iter().filter(|dt| keep(dt)).copied().collect()
+    };
+
+    let union = a.clone().union(b.clone());
+    let intersection = a.clone().intersection(b.clone());
+    let difference = a.difference(b);
+    let check = |recurrence: &dyn Fn(&chrono::DateTime<Utc>) -> bool,
+                 dates: Vec<chrono::DateTime<Utc>>,
+                 keep: &dyn Fn(&chrono::DateTime<Utc>) -> bool| {
+        assert_eq!(dates, expected(keep));
+        assert_eq!(dates, expected(recurrence));
+    };
+    check(
+        &|dt| union.matches(dt),
+        union.occurrences_between(start..end).unwrap().collect(),
+        &|dt| in_a(dt) || in_b(dt),
+    );
+    check(
+        &|dt| intersection.matches(dt),
+        intersection
+            .occurrences_between(start..end)
+            .unwrap()
+            .collect(),
+        &|dt| in_a(dt) && in_b(dt),
+    );
+    check(
+        &|dt| difference.matches(dt),
+        difference
+            .occurrences_between(start..end)
+            .unwrap()
+            .collect(),
+        &|dt| in_a(dt) && !in_b(dt),
+    );
+
+    // searching backward finds the same dates
+    let last = difference.last_in(start..end).unwrap().unwrap();
+    assert_eq!(last, Utc.with_ymd_and_hms(2024, 1, 5, 17, 45, 0).unwrap());
+    let prev = difference.prev_before(&last).unwrap();
+    assert_eq!(prev, Utc.with_ymd_and_hms(2024, 1, 5, 17, 30, 0).unwrap());
+    let prev = difference
+        .prev_before(&Utc.with_ymd_and_hms(2024, 1, 5, 13, 0, 0).unwrap())
+        .unwrap();
+    assert_eq!(prev, Utc.with_ymd_and_hms(2024, 1, 5, 11, 45, 0).unwrap());
+    let prev = intersection.prev_before(&end).unwrap();
+    assert_eq!(prev, Utc.with_ymd_and_hms(2024, 1, 5, 12, 45, 0).unwrap());
+    let prev = union.prev_at_or_before(&end).unwrap();
+    assert_eq!(prev, Utc.with_ymd_and_hms(2024, 1, 7, 12, 59, 0).unwrap());
+}
+
+#[test]
+fn test_combinators_nested_and_exhausted() {
+    let parse = |cron: &str| cron.parse::<Schedule>().unwrap();
+    let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let interval = Interval::new(&anchor, chrono::Duration::minutes(90)).unwrap();
+
+    // every 90 minutes during business hours, or at noon on weekends
+    let combined = interval
+        .intersection(parse("* 9-16 * * 1-5"))
+        .union(parse("0 12 * * 0,6"));
+    let dates: Vec<_> = combined
+        .occurrences_between(anchor..anchor + chrono::Duration::days(7))
+        .unwrap()
+        .filter(|dt| dt.weekday() == chrono::Weekday::Mon || dt.weekday() == chrono::Weekday::Sat)
+        .map(|dt| dt.format("%a %H:%M").to_string())
+        .collect();
+    assert_eq!(
+        dates,
+        vec![
+            "Mon 09:00",
+            "Mon 10:30",
+            "Mon 12:00",
+            "Mon 13:30",
+            "Mon 15:00",
+            "Mon 16:30",
+            "Sat 12:00"
+        ]
+    );
+
+    // never fires
+    let never = parse("0 9 * * *").intersection(parse("0 10 * * *"));
+    assert!(matches!(
+        never.next_after(&anchor),
+        Err(ParseError::HorizonExceeded)
+    ));
+    assert_eq!(
+        never
+            .first_in(anchor..anchor + chrono::Duration::days(30))
+            .unwrap(),
+        None
+    );
+    let never = parse("0 9 * * *").difference(parse("0 * * * *"));
+    assert!(matches!(
+        never.prev_before(&anchor),
+        Err(ParseError::HorizonExceeded)
+    ));
+}
+
+#[test]
+fn test_combinators_sub_minute() {
+    let parse = |cron: &str| cron.parse::<Schedule>().unwrap();
+    let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let at = |h, m, s| Utc.with_ymd_and_hms(2024, 1, 1, h, m, s).unwrap();
+
+    // the schedule only fires at whole minutes
+    let every_61s = Interval::new(&anchor, chrono::Duration::seconds(61)).unwrap();
+    let intersection = every_61s.intersection(parse("* * * * *"));
+    assert!(!intersection.matches(&at(0, 1, 1)));
+    assert!(intersection.matches(&at(1, 1, 0)));
+    assert_eq!(intersection.next_after(&anchor).unwrap(), at(1, 1, 0));
+
+    let every_90s = Interval::new(&at(0, 0, 30), chrono::Duration::seconds(90)).unwrap();
+    let difference = every_90s.difference(parse("* * * * *"));
+    let dates: Vec<_> = difference
+        .occurrences_between(anchor..at(0, 10, 0))
+        .unwrap()
+        .collect();
+    assert_eq!(
+        dates,
+        vec![at(0, 0, 30), at(0, 3, 30), at(0, 6, 30), at(0, 9, 30)]
+    );
+
+    let union = every_90s.union(parse("0 * * * *"));
+    assert!(union.matches(&at(0, 0, 30)));
+    assert!(!union.matches(&at(1, 0, 15)));
+    assert!(union.matches(&at(1, 0, 0)));
+}
+
+#[test]
+fn test_combinators_dst_policies() {
+    let parse = |cron: &str| cron.parse::<Schedule>().unwrap();
+    let pacific = |d, h, m| Pacific.with_ymd_and_hms(2024, 3, d, h, m, 0).unwrap();
+
+    // the run shifted out of the gap is a date of the schedule
+    let every_30m = Interval::new(&pacific(9, 0, 0), chrono::Duration::minutes(30)).unwrap();
+    for (policy, moved) in [
+        (GapPolicy::Shift, pacific(10, 3, 30)),
+        (GapPolicy::FirstValid, pacific(10, 3, 0)),
+    ] {
+        let schedule = parse("30 2 * * *").with_gap_policy(policy);
+        assert!(Recurrence::matches(&schedule, &moved));
+        let intersection = every_30m.intersection(schedule);
+        assert_eq!(intersection.next_after(&pacific(10, 0, 0)).unwrap(), moved);
+    }
+
+    // only the later of the repeated 01:30 is a date of the schedule
+    let first = Utc
+        .with_ymd_and_hms(2024, 11, 3, 8, 30, 0)
+        .unwrap()
+        .with_timezone(&Pacific);
+    let second = first + chrono::Duration::hours(1);
+    let later = parse("30 1 * * *").with_overlap_policy(OverlapPolicy::Later);
+    assert!(!Recurrence::matches(&later, &first));
+    assert!(Recurrence::matches(&later, &second));
+    let every_hour = Interval::new(&first, chrono::Duration::hours(1)).unwrap();
+    let difference = every_hour.difference(later);
+    let dates: Vec<_> = difference
+        .occurrences_between(first..first + chrono::Duration::hours(3))
+        .unwrap()
+        .collect();
+    assert_eq!(dates, vec![first, first + chrono::Duration::hours(2)]);
+}
+
+#[test]
+fn test_calendar_exclusions() {
+    let day = |month, day| chrono::NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+    let calendar = Calendar::new()
+        .exclude_date(day(3, 29))
+        .exclude_dates(day(4, 1)..=day(4, 12))
+        .exclude_dates(day(4, 10)..=day(4, 16))
+        .exclude_schedule("* 12 * * *".parse().unwrap());
+    let schedule: Schedule = "0 */4 * * 1-5".parse().unwrap();
+    let batch = schedule.clone().excluding(calendar.clone());
+    assert_eq!(batch.calendar(), &calendar);
+
+    // same dates as filtering the schedule
+    let start = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 4, 30, 0, 0, 0).unwrap();
+    let expected: Vec<_> = schedule
+        .occurrences_between(start..end)
+        .unwrap()
+        .filter(|dt| !calendar.excludes(dt))
+        .collect();
+    let dates: Vec<_> = batch.occurrences_between(start..end).unwrap().collect();
+    assert_eq!(dates, expected);
+    assert!(dates.iter().all(|dt| batch.matches(dt)));
+    assert!(!batch.matches(&Utc.with_ymd_and_hms(2024, 3, 28, 12, 0, 0).unwrap()));
+
+    // overlapping ranges are skipped at once, in both directions
+    let thursday = Utc.with_ymd_and_hms(2024, 3, 28, 20, 0, 0).unwrap();
+    let next = batch.next_after(&thursday).unwrap();
+    assert_eq!(next, Utc.with_ymd_and_hms(2024, 4, 17, 0, 0, 0).unwrap());
+    let prev = batch.prev_before(&next).unwrap();
+    assert_eq!(prev, thursday);
+    assert_eq!(
+        batch
+            .last_in(..Utc.with_ymd_and_hms(2024, 3, 28, 13, 0, 0).unwrap())
+            .unwrap(),
+        Some(Utc.with_ymd_and_hms(2024, 3, 28, 8, 0, 0).unwrap())
+    );
+}
+
+#[test]
+fn test_calendar_local_days() {
+    // 2024-12-25 in Chicago is from 06:00 UTC to 06:00 UTC the next day
+    let christmas = chrono::NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+    let batch = "0 * * * *"
+        .parse::<Schedule>()
+        .unwrap()
+        .excluding(Calendar::new().exclude_date(christmas));
+    let before = Chicago.with_ymd_and_hms(2024, 12, 24, 23, 30, 0).unwrap();
+    let next = batch.next_after(&before).unwrap();
+    assert_eq!(
+        next,
+        Chicago.with_ymd_and_hms(2024, 12, 26, 0, 0, 0).unwrap()
+    );
+    let next = batch.next_after(&before.with_timezone(&Utc)).unwrap();
+    assert_eq!(next, Utc.with_ymd_and_hms(2024, 12, 26, 0, 0, 0).unwrap());
+
+    // everything excluded
+    let never = "0 0 * * *"
+        .parse::<Schedule>()
+        .unwrap()
+        .excluding(Calendar::new().exclude_schedule("* * * * *".parse().unwrap()));
+    assert!(matches!(
+        never.next_after(&before),
+        Err(ParseError::HorizonExceeded)
+    ));
+}
+
+#[test]
+fn test_business_days() {
+    use chrono::Weekday::{Fri, Sat};
+
+    let day = |month, day| chrono::NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+    let days = BusinessDays::new()
+        .with_holiday(day(1, 1))
+        .with_holidays(day(3, 28)..=day(4, 1))
+        .with_holiday(day(12, 25));
+    let schedule: Schedule = "30 9,17 * * *".parse().unwrap();
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+    for (which, expected) in [
+        (
+            BusinessDay::FIRST,
+            [(1, 2), (2, 1), (3, 1), (4, 2), (12, 2)],
+        ),
+        (
+            BusinessDay::nth(3).unwrap(),
+            [(1, 4), (2, 5), (3, 5), (4, 4), (12, 4)],
+        ),
+        (
+            BusinessDay::LAST,
+            [(1, 31), (2, 29), (3, 27), (4, 30), (12, 31)],
+        ),
+        (
+            BusinessDay::nth_last(5).unwrap(),
+            [(1, 25), (2, 23), (3, 21), (4, 24), (12, 24)],
+        ),
+    ] {
+        let recurrence = schedule.clone().on_business_day(which, days.clone());
+        let dates: Vec<_> = recurrence
+            .occurrences_between(start..end)
+            .unwrap()
+            .collect();
+        assert_eq!(dates.len(), 24, "{which:?}");
+        assert!(dates.iter().all(|dt| recurrence.matches(dt)));
+        assert!(dates.iter().all(|dt| days.is_business_day(dt.date_naive())));
+        for (month, expected) in expected {
+            let in_month: Vec<u32> = dates
+                .iter()
+                .filter(|dt| dt.month() == month)
+                .map(Datelike::day)
+                .collect();
+            assert_eq!(in_month, vec![expected, expected], "{which:?} {month}");
+        }
+
+        // backward finds the same dates
+        let mut prev = end;
+        for date in dates.iter().rev() {
+            prev = recurrence.prev_before(&prev).unwrap();
+            assert_eq!(&prev, date);
+        }
+        assert_eq!(recurrence.last_in(start..prev).unwrap(), None);
+    }
+
+    // Friday and Saturday weekend, in local days
+    let days = BusinessDays::new().with_weekend([Fri, Sat]);
+    let last = "0 20 * * *"
+        .parse::<Schedule>()
+        .unwrap()
+        .on_business_day(BusinessDay::LAST, days);
+    let now = Chicago.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    let next = last.next_after(&now).unwrap();
+    assert_eq!(
+        next,
+        Chicago.with_ymd_and_hms(2024, 5, 30, 20, 0, 0).unwrap()
+    );
+    let next = last.next_after(&now.with_timezone(&Utc)).unwrap();
+    assert_eq!(next, Utc.with_ymd_and_hms(2024, 5, 30, 20, 0, 0).unwrap());
+    assert!(!last.matches(&Chicago.with_ymd_and_hms(2024, 5, 31, 20, 0, 0).unwrap()));
+
+    // no month has 25 business days, searches without an end stop at the
+    // horizon of a schedule
+    assert!(matches!(BusinessDay::nth(0), Err(ParseError::InvalidValue)));
+    assert!(matches!(
+        BusinessDay::nth_last(0),
+        Err(ParseError::InvalidValue)
+    ));
+    let never = schedule.on_business_day(BusinessDay::nth(25).unwrap(), BusinessDays::new());
+    assert!(matches!(
+        never.next_after(&start),
+        Err(ParseError::HorizonExceeded)
+    ));
+    assert!(matches!(
+        never.prev_before(&start),
+        Err(ParseError::HorizonExceeded)
+    ));
+    assert_eq!(never.first_in(start..end).unwrap(), None);
+    assert_eq!(never.last_in(start..end).unwrap(), None);
+
+    // a search with an end goes past the horizon
+    let leap_day = "0 9 29 2 *"
+        .parse::<Schedule>()
+        .unwrap()
+        .without_horizon()
+        .on_business_day(BusinessDay::nth_last(1).unwrap(), BusinessDays::new());
+    let far = Utc.with_ymd_and_hms(2060, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(
+        leap_day.first_in(start..far).unwrap(),
+        Some(Utc.with_ymd_and_hms(2024, 2, 29, 9, 0, 0).unwrap())
+    );
+}
+
+#[test]
+fn test_business_day_horizon() {
+    // a search without an end stops at the horizon of the schedule
+    let leap_monday = "0 9 29 2 1".parse::<Schedule>().unwrap();
+    let now = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+    let next = Utc.with_ymd_and_hms(2044, 2, 29, 9, 0, 0).unwrap();
+    for schedule in [
+        leap_monday.clone().with_horizon(30),
+        leap_monday.clone().without_horizon(),
+    ] {
+        assert_eq!(schedule.next_after(&now).unwrap(), next);
+        let last = schedule.on_business_day(BusinessDay::LAST, BusinessDays::new());
+        assert_eq!(last.next_after(&now).unwrap(), next);
+    }
+    let last = leap_monday
+        .with_horizon(10)
+        .on_business_day(BusinessDay::LAST, BusinessDays::new());
+    assert!(matches!(
+        last.next_after(&now),
+        Err(ParseError::HorizonExceeded)
+    ));
+}
+
+#[test]
+fn test_bounded() {
+    let schedule: Schedule = "0 9 * * *".parse().unwrap();
+    let start = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 6, 5, 9, 0, 0).unwrap();
+    let day = |day| Utc.with_ymd_and_hms(2024, 6, day, 9, 0, 0).unwrap();
+
+    // not_after is inclusive, not_before skips 2024-06-01 09:00
+    let window = schedule.clone().not_before(&start).not_after(&end);
+    let before = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    let runs: Vec<_> = window.occurrences_between(before..).unwrap().collect();
+    assert_eq!(runs, vec![day(2), day(3), day(4), day(5)]);
+    assert_eq!(window.next_after(&before).unwrap(), day(2));
+    assert_eq!(window.next_at_or_after(&end).unwrap(), end);
+    assert!(matches!(
+        window.next_after(&end),
+        Err(ParseError::Exhausted)
+    ));
+    assert_eq!(window.prev_before(&day(30)).unwrap(), end);
+    assert_eq!(window.prev_at_or_before(&day(2)).unwrap(), day(2));
+    assert!(matches!(
+        window.prev_before(&day(2)),
+        Err(ParseError::Exhausted)
+    ));
+    assert!(window.matches(&day(3)));
+    assert!(!window.matches(&day(1)));
+    assert!(!window.matches(&day(6)));
+
+    // runs are counted from not_before, whatever the start of the search
+    let limited = schedule.clone().max_runs(&start, 2);
+    assert_eq!(limited.next_after(&day(2)).unwrap(), day(3));
+    assert!(matches!(
+        limited.next_after(&day(3)),
+        Err(ParseError::Exhausted)
+    ));
+    assert!(limited.matches(&day(3)) && !limited.matches(&day(4)));
+    let runs: Vec<_> = limited.occurrences_between(before..).unwrap().collect();
+    assert_eq!(runs, vec![day(2), day(3)]);
+    // more runs than dates before not_after
+    let limited = window.clone().max_runs(&start, 10);
+    assert_eq!(limited.occurrences_between(before..).unwrap().count(), 4);
+    assert!(matches!(
+        limited.next_after(&end),
+        Err(ParseError::Exhausted)
+    ));
+    // no runs
+    let none = window.clone().max_runs(&start, 0);
+    assert!(matches!(
+        none.next_after(&before),
+        Err(ParseError::Exhausted)
+    ));
+    assert_eq!(none.occurrences_between(before..).unwrap().count(), 0);
+
+    // the start of the runs replaces not_before
+    let limited = window.max_runs(&day(3), 2);
+    let runs: Vec<_> = limited.occurrences_between(before..).unwrap().collect();
+    assert_eq!(runs, vec![day(3), day(4)]);
+    // the last run is found once when iterating
+    let every_minute: Schedule = "* * * * *".parse().unwrap();
+    let limited = every_minute.max_runs(&start, 10_000);
+    assert_eq!(
+        limited.occurrences_between(start..).unwrap().count(),
+        10_000
+    );
+
+    // no end
+    assert_eq!(
+        schedule
+            .clone()
+            .not_before(&start)
+            .next_after(&end)
+            .unwrap(),
+        day(6)
+    );
+    let leap_sunday: Schedule = "0 0 29 2 0".parse().unwrap();
+    assert!(matches!(
+        leap_sunday.not_after(&end).prev_before(&end),
+        Err(ParseError::HorizonExceeded)
+    ));
+
+    // bounds are instants, in any timezone
+    let anchor = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let interval = Interval::new(&anchor, chrono::Duration::hours(36)).unwrap();
+    let limited = interval.max_runs(&anchor, 3);
+    let local = anchor.with_timezone(&Chicago);
+    let runs: Vec<_> = limited.occurrences_between(local..).unwrap().collect();
+    assert_eq!(runs.len(), 3);
+    assert_eq!(
+        runs.last().unwrap(),
+        &(anchor + chrono::Duration::hours(72))
+    );
+}
+
 // 1541322900 -> 1_541_322_900
 // vim :%s/\(\d\)\(\(\d\d\d\)\+\d\@!\)\@=/\1_/g